@@ -1,3 +1,4 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::prelude::*;
 use bevy_cobweb::prelude::*;
 use bevy_cobweb_ui::prelude::*;
@@ -7,9 +8,46 @@ use crate::*;
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn spawn_day_start_ui(mut c: Commands, mut s: ResMut<SceneLoader>)
+/// Scroll distance in pixels of one mouse wheel line.
+const SCROLL_LINE_PX: f32 = 40.;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Scrolls the karma shop list with the mouse wheel, clamped so the list stays within its clipped parent.
+fn scroll_karma_shop(
+    mut wheel: EventReader<MouseWheel>,
+    mut lists: Query<(&mut Style, &Node, &Parent), With<KarmaShopList>>,
+    nodes: Query<&Node>,
+)
+{
+    let scroll: f32 = wheel
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y * SCROLL_LINE_PX,
+            MouseScrollUnit::Pixel => event.y,
+        })
+        .sum();
+    if scroll == 0. {
+        return;
+    }
+
+    for (mut style, node, parent) in lists.iter_mut() {
+        let Ok(parent_node) = nodes.get(**parent) else { continue };
+        let max_scroll = (node.size().y - parent_node.size().y).max(0.);
+        let current = match style.top {
+            Val::Px(top) => top,
+            _ => 0.,
+        };
+        style.top = Val::Px((current + scroll).clamp(-max_scroll, 0.));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn spawn_day_start_ui(mut c: Commands, mut s: ResMut<SceneLoader>, shop: Res<KarmaShop>)
 {
-    let scene = LoadableRef::new("ui.day_start", "scene");
+    let file = LoadableRef::from_file("ui.day_start");
+    let scene = file.e("scene");
     c.ui_builder(UiRoot).load_scene(&mut s, scene, |l| {
         l.despawn_on_broadcast::<GamePlay>();
 
@@ -34,25 +72,106 @@ fn spawn_day_start_ui(mut c: Commands, mut s: ResMut<SceneLoader>)
             });
         });
 
-        // todo: display upgrades (as info cards/buttons in a scroll-view)
+        l.edit("content::shop::list", |l| {
+            l.insert(KarmaShopList);
 
-        l.edit("footer::start_button", |l| {
+            for upgrade in shop.iter() {
+                l.load_scene(file.e("upgrade_scene"), |l| {
+                    l.edit("icon", |l| {
+                        l.insert_derived(LoadedUiImage { texture: upgrade.icon.clone(), ..default() });
+                    });
+                    l.edit("info::title", |l| {
+                        let name = upgrade.name.clone();
+                        let max_rank = upgrade.max_rank();
+                        l.update_on(resource_mutation::<KarmaUpgrades>(), |id| {
+                            move |mut e: TextEditor, upgrades: ReactRes<KarmaUpgrades>| {
+                                write_text!(e, id, "{} ({}/{})", name, upgrades.rank(&name), max_rank);
+                            }
+                        });
+                    });
+                    l.edit("info::description", |l| {
+                        let description = upgrade.description.clone();
+                        l.update_on((), |id| {
+                            move |mut e: TextEditor| {
+                                write_text!(e, id, "{}", description);
+                            }
+                        });
+                    });
+                    l.edit("buy_button", |l| {
+                        let name = upgrade.name.clone();
+                        let text_entity = l.get_entity("text").unwrap();
+                        l.update_on(
+                            (resource_mutation::<KarmaUpgrades>(), resource_mutation::<Karma>()),
+                            |_| {
+                                move |mut e: TextEditor,
+                                      shop: Res<KarmaShop>,
+                                      karma: ReactRes<Karma>,
+                                      upgrades: ReactRes<KarmaUpgrades>| {
+                                    let Some(upgrade) = shop.get(&name) else { return };
+                                    let Some(cost) = upgrade.next_cost(upgrades.rank(&name)) else {
+                                        write_text!(e, text_entity, "Max");
+                                        return;
+                                    };
+                                    if karma.total() >= cost {
+                                        write_text!(e, text_entity, "Buy {}", cost);
+                                    } else {
+                                        write_text!(e, text_entity, "Need {}", cost);
+                                    }
+                                }
+                            },
+                        );
+                        let name = upgrade.name.clone();
+                        l.on_pressed(move |w: &mut World| {
+                            w.syscall(name.clone(), purchase_karma_upgrade);
+                        });
+                    });
+                });
+            }
+        });
+
+        l.edit("footer::resume_button", |l| {
+            l.update_on((), |id| {
+                move |mut c: Commands, pending: Option<Res<PendingDaySnapshot>>| {
+                    let next = match pending.is_some() {
+                        true => DisplayControl::Display,
+                        false => DisplayControl::Hide,
+                    };
+                    c.entity(id).insert_reactive(next);
+                }
+            });
             l.on_pressed(|mut c: Commands| {
                 c.set_state(GameState::Play);
             });
         });
+
+        l.edit("footer::start_button", |l| {
+            l.on_pressed(
+                |mut c: Commands, pending: Option<Res<PendingDaySnapshot>>, file: Option<Res<SaveFile>>| {
+                    // Starting over abandons the day that was quit part-way through.
+                    discard_day_snapshot(&mut c, pending.as_deref(), file.as_deref());
+                    c.set_state(GameState::Play);
+                },
+            );
+        });
     });
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Marker component for the scrollable list of karma upgrades.
+#[derive(Component, Debug)]
+struct KarmaShopList;
+
+//-------------------------------------------------------------------------------------------------------------------
+
 pub struct StartUiPlugin;
 
 impl Plugin for StartUiPlugin
 {
     fn build(&self, app: &mut App)
     {
-        app.react(|rc| rc.on_persistent(broadcast::<GameDayStart>(), spawn_day_start_ui));
+        app.react(|rc| rc.on_persistent(broadcast::<GameDayStart>(), spawn_day_start_ui))
+            .add_systems(Update, scroll_karma_shop.run_if(in_state(GameState::DayStart)));
     }
 }
 
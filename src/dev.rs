@@ -137,6 +137,8 @@ fn check_dev_commands(
                 source: Entity::PLACEHOLDER,
                 target: entity,
                 damage: max / 5 + max / 7 + 1,
+                max_damage: None,
+                ability: None,
             });
         } else if *pressed == controls.screenshot {
             let time = SystemTime::now()
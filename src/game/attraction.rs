@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
 
 use crate::*;
 
@@ -12,7 +13,13 @@ fn update_transforms_for_attraction(
     clock: Res<GameClock>,
     mut pset: ParamSet<(
         Query<(Entity, &Transform, &AttractionSource)>,
-        Query<(Entity, &mut Transform, &mut Attraction, &mut Sprite)>,
+        Query<(
+            Entity,
+            &mut Transform,
+            &mut Attraction,
+            &mut Sprite,
+            Option<&StatusEffects>,
+        )>,
     )>,
 )
 {
@@ -29,22 +36,31 @@ fn update_transforms_for_attraction(
     buffer.sort_unstable_by(|a, b| b.0.cmp(&a.0));
 
     // Update transforms of attracted entities.
-    for (entity, mut transform, mut attraction, mut sprite) in pset.p1().iter_mut() {
+    for (entity, mut transform, mut attraction, mut sprite, maybe_effects) in pset.p1().iter_mut() {
         let Some((_, _, target_transform)) = buffer.iter().find(|(_, e, _)| *e == attraction.target) else {
             c.entity(entity).remove::<Attraction>();
             continue;
         };
+        if attraction.is_paused {
+            continue;
+        }
         let initial_vector = target_transform.translation - transform.translation;
 
         let vector = if initial_vector.length() > attraction.stop_distance {
             attraction.set_is_stopped(false);
 
-            let target_offset = attraction.target_offset.clamp_length(
-                attraction.stop_distance,
-                attraction.stop_distance.max(initial_vector.length() / 2.),
-            );
+            match attraction.waypoint {
+                // Waypoints route the entity around obstacles.
+                Some(waypoint) => (waypoint - transform.translation.truncate()).extend(0.),
+                None => {
+                    let target_offset = attraction.target_offset.clamp_length(
+                        attraction.stop_distance,
+                        attraction.stop_distance.max(initial_vector.length() / 2.),
+                    );
 
-            initial_vector + target_offset.extend(0.)
+                    initial_vector + target_offset.extend(0.)
+                }
+            }
         } else {
             attraction.set_is_stopped(true);
             Vec3::default()
@@ -53,9 +69,9 @@ fn update_transforms_for_attraction(
         sprite.flip_x = attraction.auto_flip_sprite && initial_vector.x < 0.;
 
         // Move the entity toward its attraction source.
-        let distance = attraction
-            .update_and_get_distance(delta)
-            .min(vector.length());
+        // - Slows and stuns reduce movement speed.
+        let speed_multiplier = maybe_effects.map(|e| e.speed_multiplier()).unwrap_or(1.);
+        let distance = (attraction.update_and_get_distance(delta) * speed_multiplier).min(vector.length());
         let direction = vector.normalize_or(Vec3::default());
         let movement = direction * distance;
         transform.translation += movement;
@@ -64,6 +80,71 @@ fn update_transforms_for_attraction(
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Pushes nearby mobs apart so they don't collapse into one pile while converging on their targets.
+///
+/// Stopped and paused mobs are not pushed, so they stay stopped (e.g. emitters waiting to fire) and behaviors
+/// that control movement aren't disrupted. Other mobs are still pushed away from them.
+fn apply_mob_separation(
+    mut buffer: Local<Vec<(Entity, Vec2)>>,
+    clock: Res<GameClock>,
+    constants: ReactRes<GameConstants>,
+    grid: Res<SpatialGrid>,
+    mut mobs: Query<(Entity, &mut Transform, &Attraction, Option<&StatusEffects>), With<Mob>>,
+)
+{
+    let radius = constants.mob_separation_radius;
+    let max_push = constants.mob_separation_strength * clock.delta.as_secs_f32();
+    if radius <= 0. || max_push <= 0. {
+        return;
+    }
+
+    // Collect pushes before applying them so the result doesn't depend on iteration order.
+    buffer.clear();
+    for (entity, transform, attraction, maybe_effects) in mobs.iter() {
+        if attraction.is_stopped() || attraction.is_paused() {
+            continue;
+        }
+        let speed_multiplier = maybe_effects.map(|e| e.speed_multiplier()).unwrap_or(1.);
+        if speed_multiplier <= 0. {
+            continue;
+        }
+
+        let location = transform.translation.truncate();
+        let mut push = Vec2::ZERO;
+        grid.for_each_in_radius(location, radius, |other| {
+            if other == entity {
+                return;
+            }
+            let Ok((_, other_transform, _, _)) = mobs.get(other) else { return };
+            let offset = location - other_transform.translation.truncate();
+            let distance = offset.length();
+            if distance >= radius {
+                return;
+            }
+
+            // Mobs at the same location are pushed in a direction derived from the entity so they still separate.
+            let direction = match distance > 0. {
+                true => offset / distance,
+                false => Vec2::from_angle(entity.index() as f32),
+            };
+            push += direction * (1. - distance / radius);
+        });
+
+        if push == Vec2::ZERO {
+            continue;
+        }
+        buffer.push((entity, push.clamp_length_max(1.) * max_push * speed_multiplier));
+    }
+
+    // Apply pushes.
+    for (entity, push) in buffer.drain(..) {
+        let Ok((_, mut transform, _, _)) = mobs.get_mut(entity) else { continue };
+        transform.translation += push.extend(0.);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Component, Debug)]
 pub struct Attraction
 {
@@ -81,6 +162,10 @@ pub struct Attraction
     current_vel: f32,
     is_stopped: bool,
     auto_flip_sprite: bool,
+    /// Set when another behavior is controlling the entity's movement.
+    is_paused: bool,
+    /// Location to move toward instead of the target, e.g. to path around obstacles.
+    waypoint: Option<Vec2>,
 }
 
 impl Attraction
@@ -107,6 +192,8 @@ impl Attraction
             stop_distance,
             is_stopped: false,
             auto_flip_sprite,
+            is_paused: false,
+            waypoint: None,
         }
     }
 
@@ -120,6 +207,32 @@ impl Attraction
         self.is_stopped
     }
 
+    pub fn is_paused(&self) -> bool
+    {
+        self.is_paused
+    }
+
+    /// Pauses attraction movement. Accelerating entities will restart from zero velocity when unpaused.
+    pub fn set_paused(&mut self, paused: bool)
+    {
+        if paused && self.acceleration != 0. {
+            self.current_vel = 0.;
+        }
+        self.is_paused = paused;
+    }
+
+    pub fn waypoint(&self) -> Option<Vec2>
+    {
+        self.waypoint
+    }
+
+    /// Sets a location to move toward instead of the target. The entity still stops based on its distance to the
+    /// target.
+    pub fn set_waypoint(&mut self, waypoint: Option<Vec2>)
+    {
+        self.waypoint = waypoint;
+    }
+
     /// Updates internal velocity and calculates distance to travel this tick.
     fn update_and_get_distance(&mut self, delta: Duration) -> f32
     {
@@ -178,7 +291,12 @@ impl Plugin for AttractionPlugin
 {
     fn build(&self, app: &mut App)
     {
-        app.add_systems(Update, update_transforms_for_attraction.in_set(AttractionUpdateSet));
+        app.add_systems(
+            Update,
+            (update_transforms_for_attraction, apply_mob_separation)
+                .chain()
+                .in_set(AttractionUpdateSet),
+        );
     }
 }
 
@@ -0,0 +1,393 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn update_bosses(
+    mut c: Commands,
+    clock: Res<GameClock>,
+    constants: ReactRes<GameConstants>,
+    animations: Res<SpriteAnimations>,
+    mob_data: Res<MobDatabase>,
+    multipliers: Res<MobStatMultipliers>,
+    mut rng: ResMut<GameRng>,
+    player: Query<(Entity, &Transform), With<Player>>,
+    mut bosses: Query<
+        (
+            Entity,
+            &mut Boss,
+            &mut Transform,
+            &mut Attraction,
+            &mut SpriteTint,
+            &AabbSize,
+            &Health,
+            &InSpawnEvent,
+            Option<&StatusEffects>,
+        ),
+        (With<Mob>, Without<Player>),
+    >,
+)
+{
+    let Ok((player_entity, player_transform)) = player.get_single() else { return };
+    let player_loc = player_transform.translation.truncate();
+    let time = clock.elapsed;
+    let delta_secs = clock.delta.as_secs_f32();
+
+    for (entity, mut boss, mut transform, mut attraction, mut tint, size, hp, in_event, maybe_effects) in
+        bosses.iter_mut()
+    {
+        let boss = &mut *boss;
+
+        // Update phase. Phases only advance.
+        let phase = boss.config.get_phase(hp);
+        if phase > boss.phase {
+            boss.phase = phase;
+            boss.attack_index = 0;
+        }
+
+        // Stunned bosses can't act.
+        if maybe_effects.map(|e| e.is_stunned()).unwrap_or(false) {
+            continue;
+        }
+
+        let Some(phase) = boss.config.phases.get(boss.phase) else { continue };
+        let cooldown = Duration::from_millis(phase.attack_cooldown_ms);
+        let boss_loc = transform.translation.truncate();
+
+        match boss.state {
+            BossState::Idle { next_attack } => {
+                if time < next_attack || phase.attacks.is_empty() {
+                    continue;
+                }
+                let attack = &phase.attacks[boss.attack_index % phase.attacks.len()];
+                boss.attack_index += 1;
+
+                match attack {
+                    BossAttack::Charge { telegraph_ms, speed_tps, duration_ms } => {
+                        // Stop and telegraph the charge.
+                        attraction.set_paused(true);
+                        tint.telegraph = Some(constants.charge_telegraph_tint);
+                        boss.state = BossState::Telegraph {
+                            end: time + Duration::from_millis(*telegraph_ms),
+                            target: player_loc,
+                            speed_tps: *speed_tps,
+                            duration: Duration::from_millis(*duration_ms),
+                        };
+                    }
+                    BossAttack::RadialBurst { count, projectile } => {
+                        // Aim the first projectile at the player.
+                        let count = (*count).max(1);
+                        let start = Dir2::new(player_loc - boss_loc).unwrap_or(Dir2::X);
+                        for i in 0..count {
+                            let angle = TAU * (i as f32) / (count as f32);
+                            let direction = Dir2::new_unchecked(Vec2::from_angle(angle).rotate(*start));
+                            projectile.create_projectile::<Player>(
+                                &mut c,
+                                &clock,
+                                &animations,
+                                entity,
+                                boss_loc,
+                                direction,
+                                &AreaSize::new(1.0),
+                                None,
+                            );
+                        }
+                        boss.state = BossState::Idle { next_attack: time + cooldown };
+                    }
+                    BossAttack::Summon { mobs, radius } => {
+                        for (count, mob_name) in mobs.iter() {
+                            let Some(data) = mob_data.get(mob_name) else {
+                                tracing::error!("failed accessing mob data for {:?}, skipping summon", mob_name);
+                                continue;
+                            };
+                            for _ in 0..*count {
+                                let angle = rng.rng().gen_range((0.)..TAU);
+                                let location = boss_loc + Vec2::from_angle(angle) * *radius;
+                                data.spawn(
+                                    &mut c,
+                                    mob_name,
+                                    rng.rng(),
+                                    &constants,
+                                    Transform::from_translation(location.extend(0.)),
+                                    player_entity,
+                                    &animations,
+                                    in_event.0,
+                                    &multipliers,
+                                    None,
+                                );
+                            }
+                        }
+                        boss.state = BossState::Idle { next_attack: time + cooldown };
+                    }
+                }
+            }
+            BossState::Telegraph { end, target, speed_tps, duration } => {
+                if time < end {
+                    continue;
+                }
+
+                // Charge toward where the player was when the telegraph started.
+                tint.telegraph = None;
+                boss.state = BossState::Charging {
+                    end: time + duration,
+                    direction: Dir2::new(target - boss_loc).unwrap_or(Dir2::X),
+                    speed_tps,
+                };
+            }
+            BossState::Charging { end, direction, speed_tps } => {
+                let offset = direction * speed_tps * delta_secs;
+                let (next_loc, stopped) = bounded_dash(&constants, boss_loc, offset, **size);
+                transform.translation = next_loc.extend(transform.translation.z);
+
+                if time < end && !stopped {
+                    continue;
+                }
+
+                attraction.set_paused(false);
+                boss.state = BossState::Idle { next_attack: time + cooldown };
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn update_active_boss(mut c: Commands, mut active: ReactResMut<ActiveBoss>, bosses: Query<(&Boss, &Health)>)
+{
+    // Only one boss is displayed at a time.
+    let next = bosses
+        .iter()
+        .filter(|(_, hp)| hp.current() > 0)
+        .map(|(boss, hp)| ActiveBoss {
+            name: Some(boss.config.name.clone()),
+            current: hp.current(),
+            max: hp.max(),
+        })
+        .next()
+        .unwrap_or_default();
+
+    if *active != next {
+        *active.get_mut(&mut c) = next;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn reset_active_boss(mut c: Commands, mut active: ReactResMut<ActiveBoss>)
+{
+    if active.name.is_some() {
+        *active.get_mut(&mut c) = ActiveBoss::default();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn handle_boss_deaths(
+    mut deaths: EventReader<EntityDeath>,
+    mut conditions: ResMut<DayEndConditions>,
+    bosses: Query<&Boss>,
+)
+{
+    for boss in deaths.read().filter_map(|death| bosses.get(**death).ok()) {
+        if boss.config.ends_day_on_death {
+            conditions.boss_defeated = true;
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Copy, Clone)]
+enum BossState
+{
+    Idle
+    {
+        next_attack: Duration
+    },
+    Telegraph
+    {
+        end: Duration,
+        target: Vec2,
+        speed_tps: f32,
+        duration: Duration,
+    },
+    Charging
+    {
+        end: Duration, direction: Dir2, speed_tps: f32
+    },
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component for boss mobs.
+#[derive(Component, Debug)]
+pub struct Boss
+{
+    config: BossConfig,
+    phase: usize,
+    attack_index: usize,
+    state: BossState,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BossAttack
+{
+    /// Stops and telegraphs, then dashes toward the player's location at the start of the telegraph.
+    Charge
+    {
+        telegraph_ms: u64, speed_tps: f32, duration_ms: u64
+    },
+    /// Fires `count` projectiles evenly around the boss, starting with one aimed at the player.
+    RadialBurst
+    {
+        count: usize, projectile: ProjectileConfig
+    },
+    /// Spawns mobs from the `MobDatabase` in a circle around the boss.
+    ///
+    /// Summoned mobs are counted as part of the boss's spawn event.
+    Summon
+    {
+        /// [ (count, mob name) ]
+        mobs: Vec<(usize, String)>,
+        radius: f32,
+    },
+}
+
+impl Default for BossAttack
+{
+    fn default() -> Self
+    {
+        Self::Summon { mobs: Vec::default(), radius: 0. }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BossPhase
+{
+    /// The phase starts when the boss's health falls to this percent of its max health.
+    pub health_threshold_percent: f32,
+    /// Attacks are used in order, looping back to the first attack.
+    pub attacks: Vec<BossAttack>,
+    /// Time between attacks.
+    pub attack_cooldown_ms: u64,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BossConfig
+{
+    /// Name displayed on the boss health bar.
+    pub name: String,
+    pub base_damage: usize,
+    pub base_cooldown_millis: u64,
+    /// Phases should be sorted by descending health threshold. The first phase should have a threshold of 100.
+    pub phases: Vec<BossPhase>,
+    /// If true, the day ends when the boss is killed.
+    #[reflect(default)]
+    pub ends_day_on_death: bool,
+}
+
+impl BossConfig
+{
+    /// The boss's damage is scaled by `damage_multiplier`.
+    pub fn setup_in_entity(
+        &self,
+        constants: &GameConstants,
+        ec: &mut EntityCommands,
+        start_pos: Vec2,
+        damage_multiplier: f32,
+    ) -> f32
+    {
+        let mut config = self.clone();
+        config.base_damage = scale_stat(config.base_damage, damage_multiplier);
+        for attack in config.phases.iter_mut().flat_map(|p| p.attacks.iter_mut()) {
+            if let BossAttack::RadialBurst { projectile, .. } = attack {
+                *projectile = projectile.clone().with_damage_multiplier(damage_multiplier);
+            }
+        }
+
+        let damage = config.base_damage;
+        ec.insert((
+            Boss {
+                config,
+                phase: 0,
+                attack_index: 0,
+                state: BossState::Idle { next_attack: Duration::default() },
+            },
+            EffectZone::<Player>::new(
+                EffectZoneConfig::ApplyAndRegen { cooldown_ms: self.base_cooldown_millis },
+                apply_collider_effect,
+            ),
+            PrevLocation(start_pos),
+            Collider { damage, max_damage: None, status_effects: Vec::default() },
+        ));
+        constants.collider_mob_stop_distance
+    }
+
+    fn get_phase(&self, hp: &Health) -> usize
+    {
+        let percent = (hp.current() as f32) * 100. / (hp.max().max(1) as f32);
+        self.phases
+            .iter()
+            .rposition(|phase| percent <= phase.health_threshold_percent)
+            .unwrap_or(0)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the boss displayed in the boss health bar.
+#[derive(ReactResource, Default, Debug, Clone, PartialEq)]
+pub struct ActiveBoss
+{
+    /// `None` if no boss is alive.
+    pub name: Option<String>,
+    pub current: usize,
+    pub max: usize,
+}
+
+impl ActiveBoss
+{
+    pub fn health_percent(&self) -> f32
+    {
+        (self.current as f32) * 100. / (self.max.max(1) as f32)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_type::<BossConfig>()
+            .init_react_resource::<ActiveBoss>()
+            .add_systems(OnExit(PlayState::Day), reset_active_boss)
+            // Both systems use `GameRng`, so they must be ordered for determinism.
+            .add_systems(
+                Update,
+                update_bosses
+                    .in_set(MobUpdateSet)
+                    .after(update_spawner_mobs),
+            )
+            .add_systems(Update, handle_boss_deaths.in_set(DamageSet::HandleDeaths))
+            .add_systems(Update, update_active_boss.in_set(DamageSet::DespawnDead));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
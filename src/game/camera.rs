@@ -1,3 +1,4 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use bevy_cobweb::prelude::*;
@@ -9,20 +10,19 @@ use crate::*;
 
 fn update_camera(
     constants: ReactRes<GameConstants>,
-    mut camera: Query<(&Camera, &GlobalTransform, &mut Transform), With<MainCamera>>,
-    window: Query<&Window, With<PrimaryWindow>>,
+    viewport: CameraViewport,
+    mut camera: Query<&mut Transform, With<MainCamera>>,
     bg: Query<(&TilemapGridSize, &TilemapType), With<BackgroundTilemap>>,
     player: Query<&Transform, (With<Player>, Without<MainCamera>)>,
 )
 {
-    let (camera, cam_global, mut camera_transform) = camera.single_mut();
-    let window = window.single();
+    let mut camera_transform = camera.single_mut();
     let (bg_grid_size, bg_tilemap_type) = bg.single();
     let player_transform = player.single();
 
     // Get starting position of camera.
     let cam_translation = &mut camera_transform.translation;
-    let (cam_lower_left, cam_upper_right) = get_camera_corners(&camera, &cam_global, &window);
+    let (cam_lower_left, cam_upper_right) = viewport.corners();
 
     // Get boundaries of map.
     let tile_radius = constants.map_tile_size.x / 2.;
@@ -63,6 +63,64 @@ pub fn get_camera_corners(camera: &Camera, cam_global: &GlobalTransform, window:
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Returns `(lower left, upper right)` corners of a virtual viewport centered on the camera.
+pub fn get_virtual_camera_corners(
+    viewport: &VirtualViewport,
+    projection: &OrthographicProjection,
+    cam_global: &GlobalTransform,
+) -> (Vec2, Vec2)
+{
+    let center = cam_global.translation().truncate();
+    let half_size = **viewport * projection.scale / 2.;
+
+    (center - half_size, center + half_size)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Overrides the primary window's dimensions when computing the main camera's viewport.
+///
+/// Insert this resource in apps that don't have a window (e.g. headless simulations).
+#[derive(Resource, Deref, Debug, Copy, Clone)]
+pub struct VirtualViewport(pub Vec2);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// System param for accessing the world-space viewport of the main camera.
+///
+/// Uses [`VirtualViewport`] if it exists, otherwise uses the primary window.
+#[derive(SystemParam)]
+pub struct CameraViewport<'w, 's>
+{
+    virtual_viewport: Option<Res<'w, VirtualViewport>>,
+    camera: Query<
+        'w,
+        's,
+        (
+            &'static Camera,
+            &'static GlobalTransform,
+            &'static OrthographicProjection,
+        ),
+        With<MainCamera>,
+    >,
+    window: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+}
+
+impl<'w, 's> CameraViewport<'w, 's>
+{
+    /// Returns `(lower left, upper right)` corners of the main camera's viewport.
+    pub fn corners(&self) -> (Vec2, Vec2)
+    {
+        let (camera, cam_global, projection) = self.camera.single();
+        match &self.virtual_viewport {
+            Some(viewport) => get_virtual_camera_corners(viewport, projection, cam_global),
+            None => get_camera_corners(camera, cam_global, self.window.single()),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(SystemSet, Hash, Eq, PartialEq, Debug, Copy, Clone)]
 pub struct CameraUpdateSet;
 
@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn update_chargers(
+    clock: Res<GameClock>,
+    constants: ReactRes<GameConstants>,
+    player: Query<&Transform, With<Player>>,
+    mut chargers: Query<
+        (
+            &mut Charger,
+            &mut Transform,
+            &mut Attraction,
+            &mut SpriteTint,
+            &AabbSize,
+            Option<&StatusEffects>,
+        ),
+        (With<Mob>, Without<Player>),
+    >,
+)
+{
+    let Ok(player_transform) = player.get_single() else { return };
+    let player_loc = player_transform.translation.truncate();
+    let time = clock.elapsed;
+    let delta_secs = clock.delta.as_secs_f32();
+
+    for (mut charger, mut transform, mut attraction, mut tint, size, maybe_effects) in chargers.iter_mut() {
+        // Stunned chargers are frozen in their current state.
+        let speed_multiplier = maybe_effects.map(|e| e.speed_multiplier()).unwrap_or(1.);
+        if maybe_effects.map(|e| e.is_stunned()).unwrap_or(false) {
+            continue;
+        }
+
+        let charger = &mut *charger;
+        let config = &charger.config;
+        let loc = transform.translation.truncate();
+
+        match charger.state {
+            ChargerState::Approaching => {
+                if time < charger.next_charge_time || loc.distance(player_loc) > config.trigger_distance {
+                    continue;
+                }
+
+                // Stop and telegraph the charge.
+                attraction.set_paused(true);
+                tint.telegraph = Some(constants.charge_telegraph_tint);
+                charger.state = ChargerState::Telegraphing {
+                    end: time + Duration::from_millis(config.telegraph_ms),
+                    target: player_loc,
+                };
+            }
+            ChargerState::Telegraphing { end, target } => {
+                if time < end {
+                    continue;
+                }
+
+                // Dash toward where the player was when the telegraph started.
+                tint.telegraph = None;
+                charger.state = ChargerState::Charging {
+                    end: time + Duration::from_millis(config.duration_ms),
+                    direction: Dir2::new(target - loc).unwrap_or(Dir2::X),
+                };
+            }
+            ChargerState::Charging { end, direction } => {
+                // Stop early if the charger runs into the map boundary.
+                let offset = direction * config.speed_tps * speed_multiplier * delta_secs;
+                let (next_loc, stopped) = bounded_dash(&constants, loc, offset, **size);
+                transform.translation = next_loc.extend(transform.translation.z);
+
+                if time < end && !stopped {
+                    continue;
+                }
+
+                charger.state = ChargerState::Recovering { end: time + Duration::from_millis(config.recover_ms) };
+            }
+            ChargerState::Recovering { end } => {
+                if time < end {
+                    continue;
+                }
+
+                attraction.set_paused(false);
+                charger.next_charge_time = time + Duration::from_millis(config.cooldown_ms);
+                charger.state = ChargerState::Approaching;
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Copy, Clone)]
+enum ChargerState
+{
+    Approaching,
+    Telegraphing
+    {
+        end: Duration,
+        target: Vec2,
+    },
+    Charging
+    {
+        end: Duration,
+        direction: Dir2,
+    },
+    Recovering
+    {
+        end: Duration,
+    },
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Settings for mobs that charge at the player.
+///
+/// The mob approaches the player with its normal attraction movement, stops and telegraphs when in range, dashes
+/// in a straight line toward where the player was when the telegraph started, then recovers before approaching
+/// again.
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChargeConfig
+{
+    /// Distance from the player where the mob starts a charge.
+    pub trigger_distance: f32,
+    pub telegraph_ms: u64,
+    pub speed_tps: f32,
+    /// Max duration of the dash.
+    pub duration_ms: u64,
+    /// Time the mob stands still after a dash.
+    pub recover_ms: u64,
+    /// Minimum time between the end of a charge and the start of the next one.
+    pub cooldown_ms: u64,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component for mobs with charging behavior.
+#[derive(Component, Debug)]
+pub struct Charger
+{
+    config: ChargeConfig,
+    state: ChargerState,
+    next_charge_time: Duration,
+}
+
+impl Charger
+{
+    pub fn new(config: ChargeConfig) -> Self
+    {
+        Self {
+            config,
+            state: ChargerState::Approaching,
+            next_charge_time: Duration::default(),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct ChargerPlugin;
+
+impl Plugin for ChargerPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_type::<ChargeConfig>()
+            .add_systems(Update, update_chargers.in_set(MobUpdateSet));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
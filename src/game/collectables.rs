@@ -1,4 +1,4 @@
-use bevy::math::bounding::IntersectsVolume;
+use bevy::math::bounding::{BoundingVolume, IntersectsVolume};
 use bevy::prelude::*;
 use bevy_cobweb::prelude::*;
 use bevy_cobweb_ui::prelude::*;
@@ -12,9 +12,9 @@ use crate::*;
 
 /// Finds dead entities with `CollectableDrop` and spawns the drop.
 ///
-/// Only considers entities that emit `EntityDeath` events. If a new kind of droppable entity is introduced then
-/// that must be handled separately.
-fn handle_collectable_drops(
+/// Handles any entity that emits `EntityDeath` events, including mobs and destructible props. Droppable entities
+/// that don't die via `Health` must be handled separately.
+pub(crate) fn handle_collectable_drops(
     mut deaths: EventReader<EntityDeath>,
     mut c: Commands,
     mut rng: ResMut<GameRng>,
@@ -47,17 +47,20 @@ fn handle_collectable_drops(
 //-------------------------------------------------------------------------------------------------------------------
 
 fn apply_collectable_effect_impl(
-    In((collectable, _player_entity)): In<(Entity, Entity)>,
+    In((collectable, player_entity)): In<(Entity, Entity)>,
     collectables: Query<&Collectable>,
     mut c: Commands,
+    mut heals: EventWriter<HealApplied>,
     constants: ReactRes<GameConstants>,
     mut player: Query<(&mut Level, &mut Health, &ExpAmp), With<Player>>,
     mut karma: ReactResMut<Karma>,
     mut powerups: ResMut<BufferedPowerUps>,
+    mut collected: EventWriter<CollectableApplied>,
 )
 {
     let Ok((mut level, mut health, exp_amp)) = player.get_single_mut() else { return };
     let Ok(collectable) = collectables.get(collectable) else { return };
+    collected.send(CollectableApplied(*collectable));
 
     // Handle type.
     match *collectable {
@@ -70,7 +73,10 @@ fn apply_collectable_effect_impl(
         }
         Collectable::HealthPack => {
             let hp = (constants.collectable_hp_max_health * (health.max() as f32)).round() as usize;
-            health.add(hp);
+            let amount = health.add(hp);
+            if amount > 0 {
+                heals.send(HealApplied { target: player_entity, amount });
+            }
         }
     }
 }
@@ -88,16 +94,23 @@ fn handle_collectable_detection(
     constants: ReactRes<GameConstants>,
     player: Query<(Entity, &CollectionRange, &Transform, &AabbSize), With<Player>>,
     collectables: Query<(Entity, &Collectable, &Transform, &AabbSize), Without<Attraction>>,
+    grid: Res<SpatialGrid>,
 )
 {
     let Ok((player_entity, range, player_transform, player_size)) = player.get_single() else { return };
     let player_aabb = player_size.get_2d(player_transform);
 
-    for (entity, collectable, collectable_transform, collectable_size) in collectables.iter() {
+    // Detection ranges are centered on collectables, so only collectables within range of the player's aabb can
+    // detect the player.
+    let range = range.current() as f32;
+    grid.for_each_in_aabb(player_aabb.grow(Vec2::splat(range)), |entity| {
+        let Ok((entity, collectable, collectable_transform, collectable_size)) = collectables.get(entity) else {
+            return;
+        };
+
         // Get collectable's detection range if allowed.
-        let Some(detection_range) = collectable.get_detection_range(range.current() as f32, **collectable_size)
-        else {
-            continue;
+        let Some(detection_range) = collectable.get_detection_range(range, **collectable_size) else {
+            return;
         };
 
         // Check for collision with the collectable's detection range.
@@ -106,7 +119,7 @@ fn handle_collectable_detection(
             .get_2d(collectable_transform)
             .bounding_circle();
         if !entity_aabb.intersects(&player_aabb) {
-            continue;
+            return;
         }
 
         // Add attraction.
@@ -118,7 +131,7 @@ fn handle_collectable_detection(
             0.,
             false,
         ));
-    }
+    });
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -192,10 +205,24 @@ impl Default for Collectable
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Event emitted when the player collects a [`Collectable`].
+#[derive(Event, Debug, Copy, Clone)]
+pub struct CollectableApplied(pub Collectable);
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Collection of collectables that can be dropped from a unit when it dies.
 #[derive(Component, Deref, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CollectableDrop(SmallVec<[Collectable; 1]>);
 
+impl CollectableDrop
+{
+    pub fn extend(&mut self, other: CollectableDrop)
+    {
+        self.0.extend(other.0);
+    }
+}
+
 //-------------------------------------------------------------------------------------------------------------------
 
 #[derive(SystemSet, Hash, Eq, PartialEq, Debug, Copy, Clone)]
@@ -210,6 +237,7 @@ impl Plugin for CollectablesPlugin
     fn build(&self, app: &mut App)
     {
         app.register_type::<Collectable>()
+            .add_state_scoped_event::<CollectableApplied>(PlayState::Day)
             .add_systems(Update, handle_collectable_detection.in_set(CollectablesUpdateSet))
             .add_systems(Update, handle_collectable_drops.in_set(DamageSet::HandleDeaths));
     }
@@ -1,31 +1,103 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
+use rand::Rng;
 
 use crate::*;
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn handle_damage_events(
+pub(crate) fn handle_damage_events(
     mut events: EventReader<DamageEvent>,
+    mut applied: EventWriter<DamageApplied>,
     mut deaths: EventWriter<EntityDeath>,
-    sources: Query<&DamageAmp>,
-    mut targets: Query<(&mut Health, &Armor)>,
+    mut status_events: EventWriter<StatusEffectEvent>,
+    mut rng: ResMut<GameRng>,
+    clock: Res<GameClock>,
+    sources: Query<(
+        Option<&DamageAmp>,
+        Option<&CritChance>,
+        Option<&CritDamage>,
+        Option<&Transform>,
+    )>,
+    mut targets: Query<(
+        &mut Health,
+        &Armor,
+        Option<&mut Invulnerability>,
+        Option<&HitKnockback>,
+        Option<&mut DamageShield>,
+    )>,
 )
 {
-    for DamageEvent { source, target, damage } in events.read() {
-        let Ok((mut hp, armor)) = targets.get_mut(*target) else { continue };
+    let time = clock.elapsed;
+
+    for DamageEvent { source, target, damage, max_damage, ability } in events.read() {
+        let Ok((mut hp, armor, maybe_invulnerability, maybe_knockback, maybe_shield)) = targets.get_mut(*target)
+        else {
+            continue;
+        };
 
         // Check if entity is already dead.
         if hp.current() == 0 {
             continue;
         }
 
+        // Check if entity is invulnerable.
+        if maybe_invulnerability
+            .as_ref()
+            .map(|i| i.is_active(time))
+            .unwrap_or(false)
+        {
+            continue;
+        }
+
+        // Roll damage within the damage range.
+        let damage = match max_damage {
+            Some(max_damage) if *max_damage > *damage => rng.rng().gen_range(*damage..=*max_damage),
+            _ => *damage,
+        };
+
         // Calculate damage to apply.
-        let damage = sources
-            .get(*source)
-            .map(|a| a.calculate_damage(*damage as f32))
-            .unwrap_or(*damage as f32);
+        let (amp, crit_chance, crit_damage, source_transform) =
+            sources.get(*source).unwrap_or((None, None, None, None));
+        let damage = amp
+            .map(|a| a.calculate_damage(damage as f32))
+            .unwrap_or(damage as f32);
+        let crit = crit_chance.map(|c| c.roll(rng.rng())).unwrap_or(false);
+        let damage = if crit {
+            crit_damage
+                .map(|c| c.calculate_damage(damage))
+                .unwrap_or(damage)
+        } else {
+            damage
+        };
         let damage = armor.calculate_damage(damage);
-        hp.remove(damage.round() as usize);
+        let amount = damage.round() as usize;
+        let amount = maybe_shield.map(|mut s| s.absorb(amount)).unwrap_or(amount);
+        hp.remove(amount);
+
+        applied.send(DamageApplied {
+            source: *source,
+            target: *target,
+            amount,
+            crit,
+            ability: ability.clone(),
+        });
+
+        // Hit reactions.
+        if amount > 0 {
+            if let Some(mut invulnerability) = maybe_invulnerability {
+                invulnerability.start(time);
+            }
+            if let (Some(knockback), Some(source_transform)) = (maybe_knockback, source_transform) {
+                status_events.send(StatusEffectEvent {
+                    source: *source,
+                    target: *target,
+                    effect: knockback.0,
+                    origin: source_transform.translation.truncate(),
+                });
+            }
+        }
 
         // Check for entity death.
         if hp.current() == 0 {
@@ -52,6 +124,121 @@ fn despawn_dead_entities(
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Blinks the sprites of invulnerable entities.
+fn update_invulnerability_blink(clock: Res<GameClock>, mut entities: Query<(&mut Invulnerability, &mut Sprite)>)
+{
+    let time = clock.elapsed;
+
+    for (mut invulnerability, mut sprite) in entities.iter_mut() {
+        let Some(start) = invulnerability.start else { continue };
+
+        // Restore the sprite when invulnerability ends.
+        if !invulnerability.is_active(time) {
+            invulnerability.start = None;
+            sprite.color.set_alpha(1.);
+            continue;
+        }
+
+        let blink_ms = invulnerability.blink.as_millis().max(1);
+        let visible = (time.saturating_sub(start).as_millis() / blink_ms) % 2 == 1;
+        sprite.color.set_alpha(if visible {
+            1.
+        } else {
+            invulnerability.blink_alpha
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component that makes an entity ignore damage for a short time after being hit.
+#[derive(Component, Debug)]
+pub struct Invulnerability
+{
+    duration: Duration,
+    blink: Duration,
+    blink_alpha: f32,
+
+    /// Start time of the current invulnerability window.
+    start: Option<Duration>,
+}
+
+impl Invulnerability
+{
+    pub fn new(duration_ms: u64, blink_ms: u64, blink_alpha: f32) -> Self
+    {
+        Self {
+            duration: Duration::from_millis(duration_ms),
+            blink: Duration::from_millis(blink_ms),
+            blink_alpha,
+            start: None,
+        }
+    }
+
+    pub fn is_active(&self, time: Duration) -> bool
+    {
+        self.start
+            .map(|start| time < start + self.duration)
+            .unwrap_or(false)
+    }
+
+    fn start(&mut self, time: Duration)
+    {
+        if self.duration.is_zero() {
+            return;
+        }
+        self.start = Some(time);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component that absorbs damage before an entity's health is reduced.
+#[derive(Component, Debug)]
+pub struct DamageShield
+{
+    current: usize,
+}
+
+impl DamageShield
+{
+    pub fn new(amount: usize) -> Self
+    {
+        Self { current: amount }
+    }
+
+    pub fn current(&self) -> usize
+    {
+        self.current
+    }
+
+    /// Returns the damage remaining after the shield absorbs what it can.
+    fn absorb(&mut self, damage: usize) -> usize
+    {
+        let absorbed = damage.min(self.current);
+        self.current -= absorbed;
+        damage - absorbed
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component that knocks an entity away from damage sources when hit.
+///
+/// Should be a [`StatusEffect::Knockback`].
+#[derive(Component, Debug)]
+pub struct HitKnockback(pub StatusEffect);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Name of the ability that spawned a damaging entity (e.g. a projectile or effect zone).
+///
+/// Copied into the [`DamageEvent::ability`] of damage the entity applies.
+#[derive(Component, Debug, Clone)]
+pub struct AbilitySource(pub String);
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Marker components for entities that should despawn when receiving `EntityDeath` events.
 #[derive(Component, Debug)]
 pub struct DespawnOnDeath;
@@ -59,12 +246,32 @@ pub struct DespawnOnDeath;
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Event sent to apply damage to an entity.
-#[derive(Event, Debug, Copy, Clone)]
+#[derive(Event, Debug, Clone)]
 pub struct DamageEvent
 {
     pub source: Entity,
     pub target: Entity,
     pub damage: usize,
+    /// If set, damage is rolled in the range `damage..=max_damage`.
+    pub max_damage: Option<usize>,
+    /// Ability that dealt the damage. See [`AbilitySource`].
+    pub ability: Option<String>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Event emitted after damage from a [`DamageEvent`] is applied to an entity.
+#[derive(Event, Debug, Clone)]
+pub struct DamageApplied
+{
+    pub source: Entity,
+    pub target: Entity,
+    /// Damage removed from the target's health, after amplification and armor.
+    pub amount: usize,
+    /// Whether the damage was a critical hit.
+    pub crit: bool,
+    /// See [`DamageEvent::ability`].
+    pub ability: Option<String>,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -97,6 +304,7 @@ impl Plugin for DamagePlugin
     fn build(&self, app: &mut App)
     {
         app.add_state_scoped_event::<DamageEvent>(PlayState::Day)
+            .add_state_scoped_event::<DamageApplied>(PlayState::Day)
             .add_state_scoped_event::<EntityDeath>(PlayState::Day)
             .configure_sets(
                 Update,
@@ -105,6 +313,7 @@ impl Plugin for DamagePlugin
                     .in_set(DamageUpdateSet),
             )
             .add_systems(Update, handle_damage_events.in_set(DamageSet::DetectDamage))
+            .add_systems(Update, update_invulnerability_blink.in_set(DamageSet::HandleDeaths))
             .add_systems(Update, despawn_dead_entities.in_set(DamageSet::DespawnDead));
     }
 }
@@ -0,0 +1,230 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Horizontal offsets cycled through when spawning numbers, so numbers spawned on the same target at the same
+/// time don't overlap completely.
+const DAMAGE_NUMBER_OFFSETS: [f32; 3] = [0., -8., 8.];
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn spawn_damage_numbers(
+    mut c: Commands,
+    mut offset_counter: Local<usize>,
+    mut damage: EventReader<DamageApplied>,
+    mut heals: EventReader<HealApplied>,
+    clock: Res<GameClock>,
+    constants: ReactRes<GameConstants>,
+    settings: ReactRes<DamageNumberSettings>,
+    fonts: Res<FontMap>,
+    mut pool: ResMut<DamageNumberPool>,
+    targets: Query<(&Transform, Option<&AabbSize>, Has<Player>), Without<DamageNumber>>,
+    mut numbers: Query<(&mut DamageNumber, &mut Text, &mut Transform, &mut Visibility)>,
+)
+{
+    if !settings.enabled {
+        damage.clear();
+        heals.clear();
+        return;
+    }
+
+    let damage = damage
+        .read()
+        .filter(|event| event.amount > 0)
+        .map(|event| (event.target, event.amount, event.crit, false));
+    let heals = heals
+        .read()
+        .map(|event| (event.target, event.amount, false, true));
+
+    for (target, amount, crit, heal) in damage.chain(heals) {
+        let Ok((target_transform, maybe_size, is_player)) = targets.get(target) else { continue };
+
+        // Select the number's style.
+        let (color, scale) = match (heal, is_player, crit) {
+            (true, _, _) => (constants.damage_number_heal_color, 1.),
+            (false, true, _) => (constants.damage_number_player_color, 1.),
+            (false, false, true) => (constants.damage_number_crit_color, constants.damage_number_crit_scale),
+            (false, false, false) => (constants.damage_number_color, 1.),
+        };
+
+        // Start above the target.
+        let offset_x = DAMAGE_NUMBER_OFFSETS[*offset_counter % DAMAGE_NUMBER_OFFSETS.len()];
+        *offset_counter += 1;
+        let height = maybe_size.map(|s| s.y / 2.).unwrap_or_default();
+        let origin = target_transform.translation.truncate() + Vec2::new(offset_x, height);
+
+        let number = DamageNumber { spawn_time: clock.elapsed, origin, color };
+        let transform = Transform::from_translation(origin.extend(0.)).with_scale(Vec3::splat(scale));
+
+        // Reuse a pooled entity if possible.
+        if let Some(entity) = pool.free.pop() {
+            if let Ok((mut prev_number, mut text, mut prev_transform, mut visibility)) = numbers.get_mut(entity) {
+                *prev_number = number;
+                *prev_transform = transform;
+                *visibility = Visibility::Inherited;
+                set_damage_number_text(&mut text, amount, heal, color);
+                pool.active.push(entity);
+                continue;
+            }
+        }
+
+        // Spawn a new entity.
+        let mut text = Text::from_section(
+            "",
+            TextStyle {
+                font: fonts.get(&constants.damage_number_font),
+                font_size: constants.damage_number_font_size,
+                color,
+            },
+        );
+        set_damage_number_text(&mut text, amount, heal, color);
+        let entity = c
+            .spawn((
+                Text2dBundle {
+                    text,
+                    text_anchor: Anchor::BottomCenter,
+                    transform,
+                    ..default()
+                },
+                SpriteLayer::DamageNumbers,
+                number,
+                StateScoped(GameState::Play),
+            ))
+            .id();
+        pool.active.push(entity);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Writes into the existing text buffer so pooled numbers don't allocate.
+fn set_damage_number_text(text: &mut Text, amount: usize, heal: bool, color: Color)
+{
+    let Some(section) = text.sections.get_mut(0) else { return };
+    section.value.clear();
+    let _ = match heal {
+        true => write!(section.value, "+{}", amount),
+        false => write!(section.value, "{}", amount),
+    };
+    section.style.color = color;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn update_damage_numbers(
+    clock: Res<GameClock>,
+    constants: ReactRes<GameConstants>,
+    mut pool: ResMut<DamageNumberPool>,
+    mut numbers: Query<(&DamageNumber, &mut Text, &mut Transform, &mut Visibility)>,
+)
+{
+    let lifetime = Duration::from_millis(constants.damage_number_lifetime_ms.max(1));
+    let DamageNumberPool { active, free } = &mut *pool;
+
+    active.retain(|entity| {
+        let Ok((number, mut text, mut transform, mut visibility)) = numbers.get_mut(*entity) else {
+            // The entity was despawned.
+            return false;
+        };
+
+        // Rise and fade out over the number's lifetime.
+        let elapsed = clock.elapsed.saturating_sub(number.spawn_time);
+        let progress = (elapsed.as_secs_f32() / lifetime.as_secs_f32()).min(1.);
+        if progress >= 1. {
+            *visibility = Visibility::Hidden;
+            free.push(*entity);
+            return false;
+        }
+
+        let rise = constants.damage_number_rise_distance * progress;
+        transform.translation = (number.origin + Vec2::new(0., rise)).extend(0.);
+        if let Some(section) = text.sections.get_mut(0) {
+            section.style.color = number.color.with_alpha(1. - progress * progress);
+        }
+        true
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Hides all damage numbers so they don't stay frozen on-screen when the day ends.
+fn recycle_damage_numbers(
+    mut pool: ResMut<DamageNumberPool>,
+    mut numbers: Query<&mut Visibility, With<DamageNumber>>,
+)
+{
+    let DamageNumberPool { active, free } = &mut *pool;
+    for entity in active.drain(..) {
+        let Ok(mut visibility) = numbers.get_mut(entity) else { continue };
+        *visibility = Visibility::Hidden;
+        free.push(entity);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn clear_damage_number_pool(mut pool: ResMut<DamageNumberPool>)
+{
+    pool.active.clear();
+    pool.free.clear();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component for floating damage number entities.
+#[derive(Component, Debug)]
+struct DamageNumber
+{
+    spawn_time: Duration,
+    origin: Vec2,
+    color: Color,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Damage number entities are reused so large numbers of hits don't allocate new entities and text buffers.
+#[derive(Resource, Default, Debug)]
+struct DamageNumberPool
+{
+    active: Vec<Entity>,
+    free: Vec<Entity>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(ReactResource, Debug)]
+pub struct DamageNumberSettings
+{
+    pub enabled: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct DamageNumbersPlugin;
+
+impl Plugin for DamageNumbersPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.insert_react_resource(DamageNumberSettings { enabled: true })
+            .init_resource::<DamageNumberPool>()
+            .add_systems(OnExit(PlayState::Day), recycle_damage_numbers)
+            .add_systems(OnExit(GameState::Play), clear_damage_number_pool)
+            .add_systems(
+                Update,
+                (spawn_damage_numbers, update_damage_numbers)
+                    .chain()
+                    .in_set(DamageSet::HandleDeaths),
+            );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
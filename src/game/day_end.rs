@@ -12,22 +12,55 @@ fn handle_day_over(mut c: Commands)
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn check_day_end_condition(mut c: Commands, constants: ReactRes<GameConstants>, game_clock: Res<GameClock>)
+fn reset_day_end_conditions(mut conditions: ResMut<DayEndConditions>)
 {
+    *conditions = DayEndConditions::default();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn check_day_end_condition(
+    mut c: Commands,
+    constants: ReactRes<GameConstants>,
+    game_clock: Res<GameClock>,
+    conditions: Res<DayEndConditions>,
+    next_state: Res<NextState<PlayState>>,
+)
+{
+    // The day is already ending (e.g. the player died last tick).
+    if let NextState::Pending(_) = *next_state {
+        return;
+    }
+
     // Condition: time ran out
-    if game_clock.elapsed_secs() >= constants.day_length_secs {
+    // Condition: a boss that ends the day was killed
+    if game_clock.elapsed_secs() >= constants.day_length_secs || conditions.boss_defeated {
         c.react().broadcast(PlayerSurvived);
     }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Day end conditions set by gameplay systems.
+///
+/// Conditions are checked in one place so only one day end event is broadcast per day.
+#[derive(Resource, Default, Debug)]
+pub struct DayEndConditions
+{
+    /// Set when a boss with `BossConfig::ends_day_on_death` is killed.
+    pub boss_defeated: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 pub struct DayEndPlugin;
 
 impl Plugin for DayEndPlugin
 {
     fn build(&self, app: &mut App)
     {
+        app.init_resource::<DayEndConditions>()
+            .add_systems(OnEnter(PlayState::Day), reset_day_end_conditions);
         app.react(|rc| {
             rc.on_persistent(
                 (broadcast::<PlayerDied>(), broadcast::<PlayerSurvived>()),
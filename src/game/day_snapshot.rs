@@ -0,0 +1,440 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const DAY_SNAPSHOT_VERSION: u32 = 2;
+
+/// Name of the save entry where day snapshots are stored. See [`SaveFile::write_entry`].
+const DAY_SNAPSHOT_ENTRY: &str = "day";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Loads a day snapshot left over from the last session so the day-start screen can offer to resume it.
+fn load_day_snapshot(
+    mut c: Commands,
+    file: Option<Res<SaveFile>>,
+    replay: Option<Res<DayReplay>>,
+    mut day: ReactResMut<Day>,
+)
+{
+    let Some(file) = file else { return };
+    if replay.is_some() {
+        return;
+    }
+
+    let raw = match file.read_entry(DAY_SNAPSHOT_ENTRY) {
+        Ok(Some(raw)) => raw,
+        Ok(None) => return,
+        Err(err) => {
+            tracing::error!("failed reading day snapshot: {}", err);
+            return;
+        }
+    };
+
+    match DaySnapshot::parse(&raw) {
+        Ok(snapshot) => {
+            tracing::info!("found day snapshot for day {}", snapshot.day);
+            day.get_mut(&mut c).set(snapshot.day);
+            c.insert_resource(PendingDaySnapshot(snapshot));
+        }
+        Err(err) => {
+            tracing::warn!("discarding unusable day snapshot: {}", err);
+            if let Err(err) = file.remove_entry(DAY_SNAPSHOT_ENTRY) {
+                tracing::error!("failed removing day snapshot: {}", err);
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Discards the pending day snapshot, e.g. when the player starts the day over instead of resuming it.
+pub fn discard_day_snapshot(c: &mut Commands, pending: Option<&PendingDaySnapshot>, file: Option<&SaveFile>)
+{
+    if pending.is_none() {
+        return;
+    }
+    c.remove_resource::<PendingDaySnapshot>();
+
+    let Some(file) = file else { return };
+    if let Err(err) = file.remove_entry(DAY_SNAPSHOT_ENTRY) {
+        tracing::error!("failed removing day snapshot: {}", err);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Restores the rng state the snapshotted day started with, so the day's obstacles and hazards are placed the same
+/// way.
+fn restore_day_start_rng(pending: Res<PendingDaySnapshot>, mut rng: ResMut<GameRng>)
+{
+    rng.set_state(&pending.day_start_rng);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn record_day_start_rng(rng: Res<GameRng>, mut day_start_rng: ResMut<DayStartRng>)
+{
+    day_start_rng.0 = rng.state();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Writes a snapshot of the current day to the save. Returns `false` if the snapshot couldn't be saved.
+///
+/// Only the state needed to continue the day is saved. Transient entities (projectiles, effects, hazards spawned
+/// by events) are dropped. The map is regenerated from the rng states it was generated with, and props that
+/// haven't been destroyed are saved.
+pub fn save_day_snapshot(
+    file: Option<Res<SaveFile>>,
+    replay: Option<Res<DayReplay>>,
+    day: ReactRes<Day>,
+    (map_rng, day_start_rng): (Res<DayMapRng>, Res<DayStartRng>),
+    rng: Res<GameRng>,
+    clock: Res<GameClock>,
+    (sequence, active_events): (Res<SpawnSequence>, Res<ActiveEvents>),
+    (karma, stats): (ReactRes<Karma>, Res<RunStats>),
+    (powerups, rerolls, buffer): (ReactRes<PlayerPowerups>, Res<PowerupRerolls>, Res<BufferedPowerUps>),
+    player: Query<(&Transform, &Health, &Level, Option<&ActiveAbilityTimers>), With<Player>>,
+    mobs: Query<
+        (
+            &Transform,
+            &MobSpawnInfo,
+            &InSpawnEvent,
+            &Health,
+            Option<&CollectableDrop>,
+        ),
+        With<Mob>,
+    >,
+    collectables: Query<(&Transform, &Collectable)>,
+    props: Query<(&Transform, &Prop, &Health, Option<&CollectableDrop>)>,
+) -> bool
+{
+    let Some(file) = file else {
+        tracing::error!("failed saving day snapshot; there is no save file");
+        return false;
+    };
+    if replay.is_some() {
+        tracing::warn!("ignoring day snapshot request during a replay");
+        return false;
+    }
+    let Ok((player_transform, health, level, ability_timers)) = player.get_single() else {
+        tracing::error!("failed saving day snapshot; player is missing");
+        return false;
+    };
+
+    let snapshot = DaySnapshot {
+        version: DAY_SNAPSHOT_VERSION,
+        day: day.get(),
+        map_rng: map_rng.0.clone(),
+        day_start_rng: day_start_rng.0.clone(),
+        rng: rng.state(),
+        clock_elapsed: clock.elapsed,
+        sequence: sequence.clone(),
+        active_events: active_events.clone(),
+        karma_collected: karma.day_collected(),
+        powerups: powerups.clone(),
+        rerolls_remaining: rerolls.remaining(),
+        buffered_powerups: buffer.pending().to_vec(),
+        player: PlayerSnapshot {
+            location: player_transform.translation.truncate(),
+            health: health.clone(),
+            level: level.clone(),
+            ability_timers: ability_timers.cloned().unwrap_or_default(),
+        },
+        mobs: mobs
+            .iter()
+            .map(|(transform, info, event, health, drop)| MobSnapshot {
+                name: info.name.clone(),
+                location: transform.translation.truncate(),
+                event_id: event.0,
+                multipliers: info.multipliers,
+                elite: info.elite.clone(),
+                health: health.clone(),
+                drop: drop.cloned(),
+            })
+            .collect(),
+        collectables: collectables
+            .iter()
+            .map(|(transform, collectable)| (*collectable, transform.translation.truncate()))
+            .collect(),
+        props: props
+            .iter()
+            .map(|(transform, prop, health, drop)| PropSnapshot {
+                name: prop.name.clone(),
+                location: transform.translation.truncate(),
+                health: health.clone(),
+                drop: drop.cloned(),
+            })
+            .collect(),
+        stats: stats.clone(),
+    };
+
+    let result = serde_json::to_string(&snapshot)
+        .map_err(|err| err.to_string())
+        .and_then(|raw| file.write_entry(DAY_SNAPSHOT_ENTRY, &raw));
+    match result {
+        Ok(()) => {
+            tracing::info!("saved day snapshot for day {} at {:?}", snapshot.day, snapshot.clock_elapsed);
+            true
+        }
+        Err(err) => {
+            tracing::error!("failed saving day snapshot: {}", err);
+            false
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Restores the pending day snapshot after the day's map and spawn sequence are set up.
+///
+/// Mobs, props, and collectables are spawned with the normal spawn helpers, then their saved state is applied on
+/// top.
+fn restore_day_snapshot(
+    mut c: Commands,
+    pending: Res<PendingDaySnapshot>,
+    file: Option<Res<SaveFile>>,
+    constants: ReactRes<GameConstants>,
+    (mob_data, prop_data): (Res<MobDatabase>, Res<PropDatabase>),
+    (images, animations): (Res<ImageMap>, Res<SpriteAnimations>),
+    mut clock: ResMut<GameClock>,
+    mut rng: ResMut<GameRng>,
+    mut karma: ReactResMut<Karma>,
+    mut powerups: ReactResMut<PlayerPowerups>,
+    (mut rerolls, mut buffer): (ResMut<PowerupRerolls>, ResMut<BufferedPowerUps>),
+    mut player: Query<(Entity, &mut Transform), With<Player>>,
+)
+{
+    let pending = pending.into_inner();
+    let snapshot = &pending.0;
+    tracing::info!("resuming day {} at {:?}", snapshot.day, snapshot.clock_elapsed);
+
+    clock.elapsed = snapshot.clock_elapsed;
+    c.insert_resource(snapshot.sequence.clone());
+    c.insert_resource(snapshot.active_events.clone());
+    c.insert_resource(snapshot.stats.clone());
+
+    // Karma banked before the day was already restored by the save.
+    karma.get_mut(&mut c).add(snapshot.karma_collected);
+    *powerups.get_mut(&mut c) = snapshot.powerups.clone();
+    *rerolls = PowerupRerolls::new(snapshot.rerolls_remaining);
+    buffer.insert(snapshot.buffered_powerups.iter().cloned());
+
+    // Player
+    let Ok((player_entity, mut player_transform)) = player.get_single_mut() else {
+        tracing::error!("failed restoring day snapshot; player is missing");
+        return;
+    };
+    player_transform.translation = snapshot
+        .player
+        .location
+        .extend(player_transform.translation.z);
+    c.entity(player_entity).insert((
+        snapshot.player.health.clone(),
+        snapshot.player.level.clone(),
+        snapshot.player.ability_timers.clone(),
+    ));
+
+    // Mobs
+    // - The spawn rng only affects randomized details (e.g. attraction offsets), so it doesn't need to be saved.
+    let mut spawn_rng = ChaCha8Rng::seed_from_u64(0);
+    for mob in snapshot.mobs.iter() {
+        let Some(data) = mob_data.get(&mob.name) else {
+            tracing::error!("failed accessing mob data for {:?}, skipping restore", mob.name);
+            continue;
+        };
+        let entity = data.spawn(
+            &mut c,
+            &mob.name,
+            &mut spawn_rng,
+            &constants,
+            Transform::from_translation(mob.location.extend(0.)),
+            player_entity,
+            &animations,
+            mob.event_id,
+            &mob.multipliers,
+            mob.elite.as_ref(),
+        );
+        let mut ec = c.entity(entity);
+        ec.insert(mob.health.clone());
+        match &mob.drop {
+            Some(drop) => ec.insert(drop.clone()),
+            None => ec.remove::<CollectableDrop>(),
+        };
+    }
+
+    // Props
+    // - Map props aren't spawned when a day is resumed, so destroyed props stay destroyed.
+    for prop in snapshot.props.iter() {
+        let Some(data) = prop_data.get(&prop.name) else {
+            tracing::error!("failed accessing prop data for {:?}, skipping restore", prop.name);
+            continue;
+        };
+        let entity = data.spawn(&mut c, &prop.name, &mut spawn_rng, &images, prop.location);
+        let mut ec = c.entity(entity);
+        ec.insert(prop.health.clone());
+        match &prop.drop {
+            Some(drop) => ec.insert(drop.clone()),
+            None => ec.remove::<CollectableDrop>(),
+        };
+    }
+
+    // Collectables
+    for (collectable, location) in snapshot.collectables.iter() {
+        collectable.spawn(&mut c, &constants, &images, *location);
+    }
+
+    rng.set_state(&snapshot.rng);
+
+    // The snapshot is consumed so the same day can't be resumed twice.
+    discard_day_snapshot(&mut c, Some(pending), file.as_deref());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// State of `GameRng` when the current day started.
+#[derive(Resource, Default, Debug)]
+pub struct DayStartRng(GameRngState);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSnapshot
+{
+    pub location: Vec2,
+    pub health: Health,
+    pub level: Level,
+    pub ability_timers: ActiveAbilityTimers,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MobSnapshot
+{
+    /// Name of the mob in the `MobDatabase`.
+    pub name: String,
+    pub location: Vec2,
+    pub event_id: usize,
+    pub multipliers: MobStatMultipliers,
+    pub elite: Option<EliteRoll>,
+    pub health: Health,
+    pub drop: Option<CollectableDrop>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropSnapshot
+{
+    /// Name of the prop in the `PropDatabase`.
+    pub name: String,
+    pub location: Vec2,
+    pub health: Health,
+    pub drop: Option<CollectableDrop>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Everything needed to continue a day that was quit part-way through.
+///
+/// Per-entity combat state isn't saved: boss phases and attack indices, charger states, spawner cooldowns, status
+/// effects, invulnerability, and damage shields are reset when the day is resumed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DaySnapshot
+{
+    pub version: u32,
+    pub day: usize,
+    /// State of `GameRng` when the day's map was generated. Used to regenerate the map's tiles and decorations.
+    pub map_rng: GameRngState,
+    /// State of `GameRng` when the day started. Used to place the day's obstacles and hazards.
+    pub day_start_rng: GameRngState,
+    /// State of `GameRng` when the snapshot was taken.
+    pub rng: GameRngState,
+    pub clock_elapsed: Duration,
+    /// Spawn events that haven't started yet.
+    pub sequence: SpawnSequence,
+    pub active_events: ActiveEvents,
+    /// Karma collected during the day. Banked karma is stored in the save.
+    pub karma_collected: usize,
+    pub powerups: PlayerPowerups,
+    pub rerolls_remaining: usize,
+    /// Power-ups that were collected but not selected yet.
+    pub buffered_powerups: Vec<PowerupSource>,
+    pub player: PlayerSnapshot,
+    pub mobs: Vec<MobSnapshot>,
+    /// [ (collectable, location) ]
+    pub collectables: Vec<(Collectable, Vec2)>,
+    /// Props that haven't been destroyed.
+    pub props: Vec<PropSnapshot>,
+    #[serde(default)]
+    pub stats: RunStats,
+}
+
+impl DaySnapshot
+{
+    fn parse(raw: &str) -> Result<Self, String>
+    {
+        let snapshot: Self = serde_json::from_str(raw).map_err(|err| err.to_string())?;
+        if snapshot.version != DAY_SNAPSHOT_VERSION {
+            return Err(format!(
+                "day snapshot version {} is not supported (expected {})",
+                snapshot.version, DAY_SNAPSHOT_VERSION
+            ));
+        }
+        Ok(snapshot)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A day snapshot loaded from the save that will be restored when the next day starts.
+#[derive(Resource, Deref, Debug)]
+pub struct PendingDaySnapshot(DaySnapshot);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Saves and resumes days that are quit part-way through.
+///
+/// Snapshots are stored next to the save in [`SaveFile`] and are deleted once resumed. Recordings of resumed days
+/// start at the resume point, so they can't be replayed.
+pub struct DaySnapshotPlugin;
+
+impl Plugin for DaySnapshotPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<DayStartRng>()
+            .add_systems(OnExit(GameState::Loading), load_day_snapshot.after(load_save))
+            .add_systems(
+                OnEnter(PlayState::Day),
+                (
+                    restore_day_start_rng
+                        .before(start_recording)
+                        .run_if(resource_exists::<PendingDaySnapshot>),
+                    record_day_start_rng.after(start_replay),
+                )
+                    .chain()
+                    .in_set(DayRngSetupSet),
+            )
+            .add_systems(
+                OnEnter(PlayState::Day),
+                restore_day_snapshot
+                    .after(reset_run_stats)
+                    .after(spawn_map_props)
+                    .after(insert_spawn_sequence)
+                    .run_if(resource_exists::<PendingDaySnapshot>),
+            );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,203 @@
+use std::time::Duration;
+
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn update_mob_stat_multipliers(
+    clock: Res<GameClock>,
+    day: ReactRes<Day>,
+    scaling: Res<DifficultyScaling>,
+    mut multipliers: ResMut<MobStatMultipliers>,
+)
+{
+    let next = scaling.multipliers(day.get(), clock.elapsed);
+    if *multipliers != next {
+        *multipliers = next;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Scales a stat by a multiplier, rounding to the nearest integer.
+pub fn scale_stat(value: usize, multiplier: f32) -> usize
+{
+    ((value as f32) * multiplier).round() as usize
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A curve that maps an input (e.g. the current day) to a stat multiplier.
+///
+/// Multipliers are never negative.
+#[derive(Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ScalingCurve
+{
+    /// Multipliers are interpolated between `(x, multiplier)` points, which should be sorted by `x`.
+    ///
+    /// Before the first point the first multiplier is used. After the last point the curve continues with the
+    /// slope of the last two points. An empty curve always equals `1.`.
+    PiecewiseLinear
+    {
+        points: Vec<(f32, f32)>
+    },
+    /// Equals `rate^(x - start)` after `start`, and `1.` before `start`.
+    Exponential
+    {
+        start: f32,
+        rate: f32,
+        #[reflect(default)]
+        max: Option<f32>,
+    },
+}
+
+impl ScalingCurve
+{
+    pub fn evaluate(&self, x: f32) -> f32
+    {
+        let multiplier = match self {
+            Self::PiecewiseLinear { points } => {
+                let Some((first, last)) = points.first().zip(points.last()) else { return 1. };
+                if x <= first.0 || points.len() == 1 {
+                    return first.1.max(0.);
+                }
+
+                // Find the segment containing x, or use the last segment to extrapolate.
+                let segment = points
+                    .windows(2)
+                    .find(|w| x <= w[1].0)
+                    .unwrap_or(&points[points.len() - 2..]);
+                let (a, b) = (segment[0], segment[1]);
+                let width = b.0 - a.0;
+                match width > 0. {
+                    true => a.1 + (b.1 - a.1) * (x - a.0) / width,
+                    false => last.1,
+                }
+            }
+            Self::Exponential { start, rate, max } => {
+                let multiplier = rate.powf((x - start).max(0.));
+                max.map(|max| multiplier.min(max)).unwrap_or(multiplier)
+            }
+        };
+        multiplier.max(0.)
+    }
+}
+
+impl Default for ScalingCurve
+{
+    fn default() -> Self
+    {
+        Self::PiecewiseLinear { points: Vec::default() }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Scaling for one mob stat. The day and time multipliers are multiplied together.
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatScaling
+{
+    /// Input is the current `Day`.
+    #[reflect(default)]
+    pub by_day: ScalingCurve,
+    /// Input is the elapsed `GameClock` time in seconds.
+    #[reflect(default)]
+    pub by_time_secs: ScalingCurve,
+}
+
+impl StatScaling
+{
+    pub fn multiplier(&self, day: usize, elapsed: Duration) -> f32
+    {
+        self.by_day.evaluate(day as f32) * self.by_time_secs.evaluate(elapsed.as_secs_f32())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Data resource that scales mob stats over days and within each day.
+///
+/// Scaling lets difficulty keep rising after the spawn schedule runs out and the last day is replayed.
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DifficultyScaling
+{
+    #[reflect(default)]
+    pub health: StatScaling,
+    #[reflect(default)]
+    pub armor: StatScaling,
+    #[reflect(default)]
+    pub damage: StatScaling,
+    #[reflect(default)]
+    pub speed: StatScaling,
+}
+
+impl DifficultyScaling
+{
+    pub fn multipliers(&self, day: usize, elapsed: Duration) -> MobStatMultipliers
+    {
+        MobStatMultipliers {
+            health: self.health.multiplier(day, elapsed),
+            armor: self.armor.multiplier(day, elapsed),
+            damage: self.damage.multiplier(day, elapsed),
+            speed: self.speed.multiplier(day, elapsed),
+        }
+    }
+}
+
+impl Command for DifficultyScaling
+{
+    fn apply(self, w: &mut World)
+    {
+        w.insert_resource(self);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Current mob stat multipliers from [`DifficultyScaling`]. Applied to mobs when they spawn.
+///
+/// Updated in `PreUpdate` before mobs are spawned.
+#[derive(Resource, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MobStatMultipliers
+{
+    pub health: f32,
+    pub armor: f32,
+    pub damage: f32,
+    pub speed: f32,
+}
+
+impl Default for MobStatMultipliers
+{
+    fn default() -> Self
+    {
+        Self { health: 1., armor: 1., damage: 1., speed: 1. }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_command::<DifficultyScaling>()
+            .init_resource::<DifficultyScaling>()
+            .init_resource::<MobStatMultipliers>()
+            .add_systems(
+                PreUpdate,
+                update_mob_stat_multipliers
+                    .before(spawn_mobs)
+                    .run_if(in_state(PlayState::Day)),
+            );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -2,7 +2,7 @@ use std::f32::consts::PI;
 use std::marker::PhantomData;
 use std::time::Duration;
 
-use bevy::math::bounding::{AabbCast2d, BoundingVolume, IntersectsVolume};
+use bevy::math::bounding::{Aabb2d, AabbCast2d, BoundingVolume, IntersectsVolume};
 use bevy::prelude::*;
 
 //use bevy_cobweb::prelude::*;
@@ -15,6 +15,8 @@ fn apply_effect_zones<T: Component>(
     mut zones: Query<(Entity, &mut EffectZone<T>, &AabbSize, &Transform, Option<&PrevLocation>), Without<T>>,
     targets: Query<(Entity, &AabbSize, &Transform), (With<T>, Without<EffectZone<T>>)>,
     clock: Res<GameClock>,
+    grid: Res<SpatialGrid>,
+    mut candidates: Local<Vec<Entity>>,
 )
 {
     let time = clock.elapsed;
@@ -43,6 +45,11 @@ fn apply_effect_zones<T: Component>(
         let last_pos = *maybe_zone_last_pos
             .cloned()
             .unwrap_or(PrevLocation(zone_transform.translation.truncate()));
+        let zone_delta = last_pos - zone_transform.translation.truncate();
+        let search_aabb = Aabb2d {
+            min: entity_aabb.min.min(entity_aabb.min + zone_delta),
+            max: entity_aabb.max.max(entity_aabb.max + zone_delta),
+        };
         let entity_aabb = AabbCast2d::new(
             entity_aabb,
             Vec2::default(),
@@ -51,6 +58,12 @@ fn apply_effect_zones<T: Component>(
             (zone_transform.translation.truncate() - last_pos).length(),
         );
 
+        // Collect nearby targets from the spatial grid.
+        candidates.clear();
+        if !matches!(zone.config, EffectZoneConfig::Target { .. }) {
+            grid.for_each_in_aabb(search_aabb, |entity| candidates.push(entity));
+        }
+
         match zone.config {
             EffectZoneConfig::Target { target, cooldown_ms } => {
                 // Check intersection with target.
@@ -69,7 +82,7 @@ fn apply_effect_zones<T: Component>(
             EffectZoneConfig::SelfDestructSingle => {
                 // Check intersection with any targets.
                 let mut count = 0;
-                for (target, aabb, transform) in targets.iter() {
+                for (target, aabb, transform) in candidates.iter().filter_map(|e| targets.get(*e).ok()) {
                     // Check intersection.
                     let target_aabb = aabb.get_2d(transform);
                     if !entity_aabb.intersects(&target_aabb) {
@@ -97,7 +110,7 @@ fn apply_effect_zones<T: Component>(
             EffectZoneConfig::SelfDestruct => {
                 // Check intersection with any targets.
                 let mut count = 0;
-                for (target, aabb, transform) in targets.iter() {
+                for (target, aabb, transform) in candidates.iter().filter_map(|e| targets.get(*e).ok()) {
                     // Check intersection.
                     let target_aabb = aabb.get_2d(transform);
                     if !entity_aabb.intersects(&target_aabb) {
@@ -123,7 +136,7 @@ fn apply_effect_zones<T: Component>(
             EffectZoneConfig::ApplyAndRegenSingle { cooldown_ms } => {
                 // Check intersection with any targets.
                 let mut count = 0;
-                for (target, aabb, transform) in targets.iter() {
+                for (target, aabb, transform) in candidates.iter().filter_map(|e| targets.get(*e).ok()) {
                     // Check intersection.
                     let target_aabb = aabb.get_2d(transform);
                     if !entity_aabb.intersects(&target_aabb) {
@@ -148,7 +161,7 @@ fn apply_effect_zones<T: Component>(
             EffectZoneConfig::ApplyAndRegen { cooldown_ms } => {
                 // Check intersection with any targets.
                 let mut count = 0;
-                for (target, aabb, transform) in targets.iter() {
+                for (target, aabb, transform) in candidates.iter().filter_map(|e| targets.get(*e).ok()) {
                     // Check intersection.
                     let target_aabb = aabb.get_2d(transform);
                     if !entity_aabb.intersects(&target_aabb) {
@@ -170,7 +183,7 @@ fn apply_effect_zones<T: Component>(
             }
             EffectZoneConfig::Continuous { cooldown_ms } => {
                 // Check intersection with any targets.
-                for (target, aabb, transform) in targets.iter() {
+                for (target, aabb, transform) in candidates.iter().filter_map(|e| targets.get(*e).ok()) {
                     // Check intersection.
                     let target_aabb = aabb.get_2d(transform);
                     if !entity_aabb.intersects(&target_aabb) {
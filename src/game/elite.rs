@@ -0,0 +1,273 @@
+use bevy::ecs::system::EntityCommands;
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Heals vampiric entities by a fraction of the damage they deal.
+fn apply_vampiric_heals(
+    mut damage: EventReader<DamageApplied>,
+    mut heals: EventWriter<HealApplied>,
+    mut sources: Query<(&Vampiric, &mut Health)>,
+)
+{
+    for DamageApplied { source, amount, .. } in damage.read() {
+        let Ok((vampiric, mut hp)) = sources.get_mut(*source) else { continue };
+
+        // Dead entities can't heal.
+        if hp.current() == 0 {
+            continue;
+        }
+
+        let heal = ((*amount as f32) * vampiric.heal_percent / 100.).round() as usize;
+        let amount = hp.add(heal);
+        if amount > 0 {
+            heals.send(HealApplied { target: *source, amount });
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn handle_elite_deaths(
+    mut deaths: EventReader<EntityDeath>,
+    mut elite_deaths: EventWriter<EliteDeath>,
+    elites: Query<&Elite>,
+)
+{
+    for death in deaths.read() {
+        let Ok(elite) = elites.get(**death) else { continue };
+        elite_deaths.send(EliteDeath { entity: **death, affixes: elite.affixes.clone() });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Color that sprites return to when temporary tints (e.g. from status effects) end.
+///
+/// Entities without this component return to `Color::WHITE`. See [`SpriteTint`].
+#[derive(Component, Debug, Copy, Clone)]
+pub struct BaseTint(pub Color);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component for vampiric entities.
+#[derive(Component, Debug)]
+pub struct Vampiric
+{
+    /// Percent of damage dealt that is converted to health.
+    heal_percent: f32,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component for elite mobs.
+#[derive(Component, Debug)]
+pub struct Elite
+{
+    affixes: Vec<EliteAffix>,
+}
+
+impl Elite
+{
+    pub fn affixes(&self) -> &[EliteAffix]
+    {
+        &self.affixes
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Event emitted when an elite mob dies.
+///
+/// This is emitted in `DamageSet::HandleDeaths` alongside the mob's `EntityDeath` event.
+#[derive(Event, Debug, Clone)]
+pub struct EliteDeath
+{
+    pub entity: Entity,
+    pub affixes: Vec<EliteAffix>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EliteAffix
+{
+    /// Only applies the affix's stat multipliers.
+    Armored,
+    /// Only applies the affix's stat multipliers.
+    Fast,
+    /// Heals `heal_percent` (0-100) of the damage the mob deals.
+    Vampiric
+    {
+        heal_percent: f32
+    },
+    /// Explodes on death like a mob with `MobOnDeathType::Explode`.
+    Explosive
+    {
+        base_damage: usize,
+        base_range: f32,
+        explosion_animation: String,
+    },
+    /// Absorbs `shield` damage before the mob's health is reduced.
+    Shielded
+    {
+        shield: usize
+    },
+}
+
+impl EliteAffix
+{
+    fn setup_in_entity(&self, ec: &mut EntityCommands, damage_multiplier: f32)
+    {
+        match self.clone() {
+            Self::Armored | Self::Fast => (),
+            Self::Vampiric { heal_percent } => {
+                ec.insert(Vampiric { heal_percent });
+            }
+            Self::Explosive { base_damage, base_range, explosion_animation } => {
+                let base_damage = scale_stat(base_damage, damage_multiplier);
+                ec.insert(Exploder::new(base_damage, base_range, explosion_animation));
+            }
+            Self::Shielded { shield } => {
+                ec.insert(DamageShield::new(shield));
+            }
+        }
+    }
+}
+
+impl Default for EliteAffix
+{
+    fn default() -> Self
+    {
+        Self::Armored
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EliteAffixConfig
+{
+    pub affix: EliteAffix,
+    pub health_multiplier: f32,
+    pub armor_multiplier: f32,
+    pub speed_multiplier: f32,
+    /// Tint applied to the elite's sprite. If an elite has multiple affixes, the first affix's tint is used.
+    pub tint: Color,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Affixes and modifiers rolled for an elite mob.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EliteRoll
+{
+    pub affixes: Vec<EliteAffix>,
+    pub health_multiplier: f32,
+    pub armor_multiplier: f32,
+    pub speed_multiplier: f32,
+    pub tint: Color,
+    pub scale: f32,
+    pub extra_drop_rolls: usize,
+}
+
+impl EliteRoll
+{
+    /// Damage from affixes is scaled by `damage_multiplier`.
+    pub fn setup_in_entity(&self, ec: &mut EntityCommands, damage_multiplier: f32)
+    {
+        for affix in self.affixes.iter() {
+            affix.setup_in_entity(ec, damage_multiplier);
+        }
+        ec.insert((Elite { affixes: self.affixes.clone() }, BaseTint(self.tint)));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Settings for elite mobs.
+///
+/// The chance for a mob to spawn as an elite is set with `SpawnSequence::elite_chance` and
+/// `SpawnEvent::elite_chance`.
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EliteSettings
+{
+    pub affixes: Vec<EliteAffixConfig>,
+    /// Max number of affixes rolled for each elite. Elites always have at least one affix.
+    pub max_affixes: usize,
+    /// Scale applied to elite sprites and hitboxes.
+    pub scale: f32,
+    /// Number of extra times an elite selects drops from its mob's drop table.
+    pub extra_drop_rolls: usize,
+}
+
+impl EliteSettings
+{
+    /// Rolls affixes for a new elite. Returns `None` if there are no affixes.
+    pub fn roll(&self, rng: &mut ChaCha8Rng) -> Option<EliteRoll>
+    {
+        if self.affixes.is_empty() {
+            return None;
+        }
+
+        let max_affixes = self.max_affixes.clamp(1, self.affixes.len());
+        let num_affixes = rng.gen_range(1..=max_affixes);
+        let mut roll = EliteRoll {
+            affixes: Vec::with_capacity(num_affixes),
+            health_multiplier: 1.,
+            armor_multiplier: 1.,
+            speed_multiplier: 1.,
+            tint: Color::WHITE,
+            scale: self.scale,
+            extra_drop_rolls: self.extra_drop_rolls,
+        };
+
+        for (i, config) in self.affixes.choose_multiple(rng, num_affixes).enumerate() {
+            if i == 0 {
+                roll.tint = config.tint;
+            }
+            roll.affixes.push(config.affix.clone());
+            roll.health_multiplier *= config.health_multiplier;
+            roll.armor_multiplier *= config.armor_multiplier;
+            roll.speed_multiplier *= config.speed_multiplier;
+        }
+
+        Some(roll)
+    }
+}
+
+impl Command for EliteSettings
+{
+    fn apply(self, w: &mut World)
+    {
+        w.insert_resource(self);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct ElitePlugin;
+
+impl Plugin for ElitePlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_command::<EliteSettings>()
+            .init_resource::<EliteSettings>()
+            .add_state_scoped_event::<EliteDeath>(PlayState::Day)
+            .add_systems(
+                Update,
+                (apply_vampiric_heals, handle_elite_deaths).in_set(DamageSet::HandleDeaths),
+            );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
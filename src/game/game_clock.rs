@@ -48,6 +48,12 @@ fn update_game_clock(mut c: Commands, time: Res<Time<Virtual>>, mut clock: ResMu
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// System set in `PreUpdate` where `GameClock` is updated.
+#[derive(SystemSet, Hash, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct GameClockUpdateSet;
+
+//-------------------------------------------------------------------------------------------------------------------
+
 pub struct GameClockPlugin;
 
 impl Plugin for GameClockPlugin
@@ -56,7 +62,12 @@ impl Plugin for GameClockPlugin
     {
         app.init_resource::<GameClock>()
             .add_systems(OnExit(GameState::Play), reset_game_clock)
-            .add_systems(PreUpdate, update_game_clock.run_if(in_state(PlayState::Day)));
+            .add_systems(
+                PreUpdate,
+                update_game_clock
+                    .in_set(GameClockUpdateSet)
+                    .run_if(in_state(PlayState::Day)),
+            );
     }
 }
 
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Max number of attempts to find a location outside the player's clear radius for each hazard spawned near the
+/// player.
+const MAX_PLACEMENT_ATTEMPTS: usize = 20;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_hazard_effect_impl(
+    In((source, target)): In<(Entity, Entity)>,
+    mut events: EventWriter<DamageEvent>,
+    mut status_events: EventWriter<StatusEffectEvent>,
+    hazards: Query<(&Hazard, &Transform)>,
+)
+{
+    let Ok((hazard, transform)) = hazards.get(source) else { return };
+
+    // Hazards that only apply status effects shouldn't produce damage events.
+    if hazard.damage > 0 {
+        events.send(DamageEvent {
+            source,
+            target,
+            damage: hazard.damage,
+            max_damage: None,
+            ability: None,
+        });
+    }
+
+    let origin = transform.translation.truncate();
+    for effect in hazard.status_effects.iter() {
+        status_events.send(StatusEffectEvent { source, target, effect: *effect, origin });
+    }
+}
+
+fn apply_hazard_effect(hazard: Entity, target: Entity, c: &mut Commands)
+{
+    c.syscall((hazard, target), apply_hazard_effect_impl);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Places the current map theme's hazards.
+pub(crate) fn spawn_map_hazards(
+    mut c: Commands,
+    mut rng: ResMut<GameRng>,
+    clock: Res<GameClock>,
+    day: ReactRes<Day>,
+    constants: ReactRes<GameConstants>,
+    images: Res<ImageMap>,
+    themes: Res<MapThemes>,
+    database: Res<HazardDatabase>,
+)
+{
+    let Some(theme) = themes.get(day.get()) else { return };
+    let rng = rng.rng();
+
+    for (count, name) in theme.hazards.iter() {
+        let Some(data) = database.get(name) else {
+            tracing::error!("failed accessing hazard data for {:?}, skipping hazard", name);
+            continue;
+        };
+
+        for _ in 0..*count {
+            let Some(location) = random_map_location(rng, &constants, data.hitbox(), theme.clear_radius) else {
+                continue;
+            };
+            data.spawn(&mut c, &clock, &images, location);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn despawn_expired_hazards(mut c: Commands, clock: Res<GameClock>, hazards: Query<(Entity, &Hazard)>)
+{
+    for (entity, hazard) in hazards.iter() {
+        let Some(despawn_time) = hazard.despawn_time else { continue };
+        if despawn_time <= clock.elapsed {
+            c.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component for hazard zones. Hazards apply their effects to both players and mobs.
+#[derive(Component, Debug)]
+pub struct Hazard
+{
+    damage: usize,
+    status_effects: Vec<StatusEffect>,
+    despawn_time: Option<Duration>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HazardData
+{
+    /// Image from the `ImageMap`.
+    pub image: String,
+    /// Size of the displayed image.
+    pub size: Vec2,
+    /// Defaults to `size`.
+    #[reflect(default)]
+    pub hitbox: Option<Vec2>,
+    #[reflect(default)]
+    pub tint: Color,
+    /// Damage applied to each intersected entity every `cooldown_ms`.
+    #[reflect(default)]
+    pub damage: usize,
+    /// Status effects applied to each intersected entity every `cooldown_ms`.
+    #[reflect(default)]
+    pub status_effects: Vec<StatusEffect>,
+    /// Cooldown per intersected entity.
+    pub cooldown_ms: u64,
+    /// Hazards without a lifetime last until the end of the day.
+    #[reflect(default)]
+    pub lifetime_ms: Option<u64>,
+}
+
+impl HazardData
+{
+    pub fn hitbox(&self) -> Vec2
+    {
+        self.hitbox.unwrap_or(self.size)
+    }
+
+    pub fn spawn(&self, c: &mut Commands, clock: &GameClock, images: &ImageMap, location: Vec2) -> Entity
+    {
+        let cooldown_ms = self.cooldown_ms;
+        c.spawn((
+            Hazard {
+                damage: self.damage,
+                status_effects: self.status_effects.clone(),
+                despawn_time: self
+                    .lifetime_ms
+                    .map(|ms| clock.elapsed + Duration::from_millis(ms)),
+            },
+            EffectZone::<Player>::new(EffectZoneConfig::Continuous { cooldown_ms }, apply_hazard_effect),
+            EffectZone::<Mob>::new(EffectZoneConfig::Continuous { cooldown_ms }, apply_hazard_effect),
+            SpriteBundle {
+                texture: images.get(&self.image),
+                sprite: Sprite { color: self.tint, custom_size: Some(self.size), ..default() },
+                transform: Transform::from_translation(location.extend(0.)),
+                ..default()
+            },
+            AabbSize(self.hitbox()),
+            SpriteLayer::GroundEffect,
+            StateScoped(GameState::Play),
+        ))
+        .id()
+    }
+
+    /// Spawns the hazard at a random location within `radius` of `center`, kept inside the map.
+    ///
+    /// The hazard is kept `GameConstants::hazard_player_clear_radius` away from `center` so it can't appear on top
+    /// of the player. Returns `None` if no location was found.
+    pub fn spawn_near(
+        &self,
+        c: &mut Commands,
+        rng: &mut ChaCha8Rng,
+        clock: &GameClock,
+        constants: &GameConstants,
+        images: &ImageMap,
+        center: Vec2,
+        radius: f32,
+    ) -> Option<Entity>
+    {
+        let min_distance = constants.hazard_player_clear_radius.max(0.) + self.hitbox().length() / 2.;
+        let bounds = (map_area_half_size(constants) - self.hitbox() / 2.).max(Vec2::ZERO);
+        let location = (0..MAX_PLACEMENT_ATTEMPTS)
+            .map(|_| {
+                let direction = rng.gen_range((0.)..TAU);
+                let distance = rng.gen_range(min_distance..=radius.max(min_distance));
+                (center + Vec2::from_angle(direction) * distance).clamp(-bounds, bounds)
+            })
+            // Clamping to the map can pull the hazard back toward the player.
+            .find(|location| location.distance(center) >= min_distance)?;
+        Some(self.spawn(c, clock, images, location))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// [hazard name, hazard data]
+#[derive(Resource, Deref, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HazardDatabase(HashMap<String, HazardData>);
+
+impl Command for HazardDatabase
+{
+    fn apply(self, w: &mut World)
+    {
+        w.insert_resource(self);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct HazardsPlugin;
+
+impl Plugin for HazardsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_command::<HazardDatabase>()
+            .init_resource::<HazardDatabase>()
+            // Hazards use `GameRng`, so they must be placed after the day's rng state is recorded or restored.
+            .add_systems(
+                OnEnter(PlayState::Day),
+                spawn_map_hazards
+                    .after(DayRngSetupSet)
+                    .after(spawn_obstacles),
+            )
+            .add_systems(Update, despawn_expired_hazards.in_set(EffectUpdateSet));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
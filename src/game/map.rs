@@ -193,7 +193,7 @@ fn spawn_map_controls(mut c: Commands, constants: ReactRes<GameConstants>, image
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn force_in_map_bounds(
+pub(crate) fn force_in_map_bounds(
     mut q: Query<(&mut Transform, &AabbSize), With<BoundInMap>>,
     constants: ReactRes<GameConstants>,
 )
@@ -234,6 +234,20 @@ pub fn map_area_half_size(constants: &GameConstants) -> Vec2
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Moves a dashing entity with hitbox `size` from `location` by `offset`, stopping at the map boundary.
+///
+/// Returns the new location, and `true` if the boundary stopped the dash. Dashes should end early when stopped
+/// so entities don't grind against the boundary, which is enforced separately by [`BoundInMap`].
+pub fn bounded_dash(constants: &GameConstants, location: Vec2, offset: Vec2, size: Vec2) -> (Vec2, bool)
+{
+    let bounds = Aabb2d::new(Vec2::ZERO, ((map_area_size(constants) - size) / 2.).max(Vec2::ZERO));
+    let next = location + offset;
+    let bounded = bounds.closest_point(next);
+    (bounded, bounded != next)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Marker component for the tilemap that controls the background.
 #[derive(Component)]
 pub struct BackgroundTilemap;
@@ -261,7 +275,7 @@ impl Plugin for MapPlugin
     fn build(&self, app: &mut App)
     {
         app.add_systems(
-            // Run all these systems on startup so the map doesn't need to be regenerated every time.
+            // Spawn the map entities once. Tiles are regenerated each day by `MapGenerationPlugin`.
             OnExit(GameState::Loading),
             (spawn_map, setup_map_boundary, spawn_map_controls).chain(),
         )
@@ -0,0 +1,423 @@
+use std::f32::consts::TAU;
+
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Hashes a lattice point to a value in `[0, 1)`.
+fn lattice_value(seed: u64, x: i32, y: i32) -> f32
+{
+    let mut h = seed
+        ^ (x as i64 as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+        ^ (y as i64 as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    h ^= h >> 33;
+    ((h >> 40) as f32) / ((1u64 << 24) as f32)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Smoothly interpolated value noise in `[0, 1)`.
+fn value_noise(seed: u64, p: Vec2) -> f32
+{
+    let cell = p.floor();
+    let f = p - cell;
+    let u = f * f * (Vec2::splat(3.) - 2. * f);
+    let (x, y) = (cell.x as i32, cell.y as i32);
+
+    let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+    let bottom = lerp(lattice_value(seed, x, y), lattice_value(seed, x + 1, y), u.x);
+    let top = lerp(lattice_value(seed, x, y + 1), lattice_value(seed, x + 1, y + 1), u.x);
+    lerp(bottom, top, u.y)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Value noise with a few octaves of detail, normalized to `[0, 1)`.
+fn fractal_noise(seed: u64, p: Vec2) -> f32
+{
+    const OCTAVES: u32 = 3;
+
+    let mut total = 0.;
+    let mut weight = 0.;
+    for octave in 0..OCTAVES {
+        let amplitude = 0.5f32.powi(octave as i32);
+        let frequency = (1u32 << octave) as f32;
+        total += amplitude * value_noise(seed.wrapping_add(octave as u64), p * frequency);
+        weight += amplitude;
+    }
+    total / weight
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn select_tile(tiles: &[ThemeTile], rng: &mut ChaCha8Rng) -> Option<u32>
+{
+    let total: f32 = tiles.iter().map(|t| t.frequency).sum();
+    if total <= 0. {
+        return tiles.first().map(|t| t.index);
+    }
+
+    let selection = rng.gen_range(0.0..total);
+    let mut cumulative = 0.;
+    for tile in tiles.iter() {
+        cumulative += tile.frequency;
+        if cumulative > selection {
+            return Some(tile.index);
+        }
+    }
+
+    tiles.last().map(|t| t.index)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A path crossing the map from one side to the other, in tile coordinates.
+struct GeneratedPath
+{
+    horizontal: bool,
+    start: f32,
+    end: f32,
+    wobble: f32,
+    waves: f32,
+    phase: f32,
+    half_width: f32,
+}
+
+impl GeneratedPath
+{
+    fn new(config: &PathConfig, size: UVec2, rng: &mut ChaCha8Rng) -> Self
+    {
+        let horizontal = rng.gen_bool(0.5);
+        let across = if horizontal { size.y } else { size.x } as f32;
+
+        // Keep path ends away from the edges of the map.
+        let range = (across * 0.2)..(across * 0.8);
+        Self {
+            horizontal,
+            start: rng.gen_range(range.clone()),
+            end: rng.gen_range(range),
+            wobble: config.wobble,
+            waves: rng.gen_range(0.5..2.0),
+            phase: rng.gen_range(0.0..TAU),
+            half_width: config.width / 2.,
+        }
+    }
+
+    fn contains(&self, pos: UVec2, size: UVec2) -> bool
+    {
+        let (along, across, length) = match self.horizontal {
+            true => (pos.x, pos.y, size.x),
+            false => (pos.y, pos.x, size.y),
+        };
+        let t = (along as f32) / (length.max(1) as f32);
+        let line = self.start + (self.end - self.start) * t;
+        let center = line + self.wobble * (t * self.waves * TAU + self.phase).sin();
+        ((across as f32) - center).abs() <= self.half_width
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn spawn_decorations(
+    c: &mut Commands,
+    rng: &mut ChaCha8Rng,
+    images: &ImageMap,
+    config: &DecorationConfig,
+    half_size: Vec2,
+)
+{
+    let texture = images.get(&config.image);
+
+    for _ in 0..config.clusters {
+        let center = Vec2 {
+            x: rng.gen_range(-half_size.x..=half_size.x),
+            y: rng.gen_range(-half_size.y..=half_size.y),
+        };
+        let count = rng.gen_range(config.min_per_cluster..=config.max_per_cluster.max(config.min_per_cluster));
+
+        for _ in 0..count {
+            // Uniform sample within the cluster's radius.
+            let angle = rng.gen_range(0.0..TAU);
+            let distance = config.cluster_radius * rng.gen_range(0.0f32..1.).sqrt();
+            let location = (center + Vec2::from_angle(angle) * distance).clamp(-half_size, half_size);
+
+            c.spawn((
+                MapDecoration,
+                SpriteBundle {
+                    texture: texture.clone(),
+                    sprite: Sprite {
+                        color: config.tint,
+                        custom_size: Some(config.size),
+                        flip_x: rng.gen_bool(0.5),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(location.extend(0.)),
+                    ..default()
+                },
+                SpriteLayer::BackgroundBillboard,
+            ));
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Selects a random location in the map for an object of `size`, outside `clear_radius` of the map's center.
+///
+/// Returns `None` if no location was found after a few attempts.
+pub fn random_map_location(
+    rng: &mut ChaCha8Rng,
+    constants: &GameConstants,
+    size: Vec2,
+    clear_radius: f32,
+) -> Option<Vec2>
+{
+    const MAX_PLACEMENT_ATTEMPTS: usize = 20;
+
+    let bounds = (map_area_half_size(constants) - size / 2.).max(Vec2::ZERO);
+    (0..MAX_PLACEMENT_ATTEMPTS)
+        .map(|_| Vec2 {
+            x: rng.gen_range(-bounds.x..=bounds.x),
+            y: rng.gen_range(-bounds.y..=bounds.y),
+        })
+        .find(|location| location.length() >= clear_radius)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Generates the current day's map layout from its [`MapTheme`].
+///
+/// Resumed days are generated from the rng state their map was originally generated with.
+fn regenerate_map(
+    mut c: Commands,
+    mut rng: ResMut<GameRng>,
+    pending_snapshot: Option<Res<PendingDaySnapshot>>,
+    mut map_rng: ResMut<DayMapRng>,
+    day: ReactRes<Day>,
+    constants: ReactRes<GameConstants>,
+    themes: Res<MapThemes>,
+    images: Res<ImageMap>,
+    mut tilemaps: Query<(&TileStorage, &TilemapSize, &mut TilemapTexture), With<BackgroundTilemap>>,
+    mut tiles: Query<&mut TileTextureIndex>,
+    decorations: Query<Entity, With<MapDecoration>>,
+)
+{
+    if let Some(pending) = pending_snapshot {
+        rng.set_state(&pending.map_rng);
+    }
+    map_rng.0 = rng.state();
+
+    let Some(theme) = themes.get(day.get()) else { return };
+    let Ok((storage, size, mut texture)) = tilemaps.get_single_mut() else { return };
+
+    for entity in decorations.iter() {
+        c.entity(entity).despawn_recursive();
+    }
+
+    // Generate from a dedicated rng so the amount of randomness used by a theme doesn't depend on the map size.
+    let seed = rng.rng().gen::<u64>() ^ (day.get() as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    let mut map_rng = ChaCha8Rng::seed_from_u64(seed);
+    let noise_seed = map_rng.gen::<u64>();
+
+    *texture = TilemapTexture::Single(images.get(&theme.tile_texture));
+
+    let size = UVec2 { x: size.x, y: size.y };
+    let paths: Vec<GeneratedPath> = theme
+        .paths
+        .iter()
+        .flat_map(|config| (0..config.count).map(move |_| config))
+        .map(|config| GeneratedPath::new(config, size, &mut map_rng))
+        .collect();
+
+    let noise_scale = theme.noise_scale.max(1.);
+    for x in 0..size.x {
+        for y in 0..size.y {
+            let Some(tile_entity) = storage.get(&TilePos { x, y }) else { continue };
+            let Ok(mut index) = tiles.get_mut(tile_entity) else { continue };
+
+            let pos = UVec2 { x, y };
+            let on_path = theme
+                .paths
+                .as_ref()
+                .filter(|_| paths.iter().any(|p| p.contains(pos, size)));
+            let selected = match on_path {
+                Some(config) => select_tile(&config.tiles, &mut map_rng),
+                None => {
+                    let noise = fractal_noise(noise_seed, pos.as_vec2() / noise_scale);
+                    theme
+                        .regions
+                        .iter()
+                        .find(|r| noise < r.max_noise)
+                        .or(theme.regions.last())
+                        .and_then(|r| select_tile(&r.tiles, &mut map_rng))
+                }
+            };
+
+            if let Some(selected) = selected {
+                index.0 = selected;
+            }
+        }
+    }
+
+    let half_size = map_area_half_size(&constants);
+    for config in theme.decorations.iter() {
+        spawn_decorations(&mut c, &mut map_rng, &images, config, half_size);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Marker component for decorations spawned by the map generator.
+#[derive(Component)]
+pub struct MapDecoration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A tile within a theme's tile spritesheet.
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThemeTile
+{
+    /// Index of the tile in the spritesheet.
+    pub index: u32,
+    /// Relative frequency of the tile within its region.
+    pub frequency: f32,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A region of the map selected by noise value.
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TileRegion
+{
+    /// Tiles belong to the first region whose `max_noise` is above the tile's noise value (noise is in
+    /// `[0, 1)`). Tiles above every region's `max_noise` use the last region.
+    pub max_noise: f32,
+    pub tiles: Vec<ThemeTile>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Paths that cross the map from one side to the other.
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathConfig
+{
+    pub count: usize,
+    /// Width of paths in tiles.
+    pub width: f32,
+    /// Max distance in tiles that paths wander from a straight line.
+    pub wobble: f32,
+    pub tiles: Vec<ThemeTile>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Clusters of decoration sprites drawn on top of the map tiles.
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecorationConfig
+{
+    pub image: String,
+    pub size: Vec2,
+    #[reflect(default)]
+    pub tint: Color,
+    pub clusters: usize,
+    pub min_per_cluster: usize,
+    pub max_per_cluster: usize,
+    pub cluster_radius: f32,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Selects the tiles and decorations used to generate the map for a day.
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapTheme
+{
+    /// Spritesheet of background tiles. Must be loaded with `LoadImages`.
+    pub tile_texture: String,
+    /// Size in tiles of noise features. Larger values produce larger regions.
+    pub noise_scale: f32,
+    /// Should be sorted by `max_noise`.
+    pub regions: Vec<TileRegion>,
+    #[reflect(default)]
+    pub paths: Option<PathConfig>,
+    #[reflect(default)]
+    pub decorations: Vec<DecorationConfig>,
+    /// [ (count, hazard name) ]
+    ///
+    /// Hazards placed at random locations at the start of the day. See [`HazardDatabase`].
+    #[reflect(default)]
+    pub hazards: Vec<(usize, String)>,
+    /// [ (count, prop name) ]
+    ///
+    /// Destructible props placed at random locations at the start of the day. See [`PropDatabase`].
+    #[reflect(default)]
+    pub props: Vec<(usize, String)>,
+    /// Hazards and props won't be placed within this distance of the center of the map, where the player spawns.
+    #[reflect(default)]
+    pub clear_radius: f32,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Map themes for each day. Days past the last theme cycle through the themes again.
+///
+/// If there are no themes, the map generated at startup is kept.
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapThemes
+{
+    pub themes: Vec<MapTheme>,
+}
+
+impl MapThemes
+{
+    pub fn get(&self, day: usize) -> Option<&MapTheme>
+    {
+        if self.themes.is_empty() {
+            return None;
+        }
+        self.themes.get(day.saturating_sub(1) % self.themes.len())
+    }
+}
+
+impl Command for MapThemes
+{
+    fn apply(self, w: &mut World)
+    {
+        w.insert_resource(self);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// State of `GameRng` when the current day's map was generated.
+#[derive(Resource, Default, Debug)]
+pub struct DayMapRng(pub GameRngState);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct MapGenerationPlugin;
+
+impl Plugin for MapGenerationPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_command::<MapThemes>()
+            .init_resource::<MapThemes>()
+            .init_resource::<DayMapRng>()
+            .react(|rc| rc.on_persistent(broadcast::<GameDayStart>(), regenerate_map));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
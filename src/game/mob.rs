@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::f32::consts::TAU;
 use std::time::Duration;
 
 use bevy::ecs::system::EntityCommands;
@@ -19,18 +20,26 @@ fn update_emitter_mobs(
     clock: Res<GameClock>,
     animations: Res<SpriteAnimations>,
     player: Query<&Transform, With<Player>>,
-    mut emitters: Query<(Entity, &mut Emitter, &Transform, &Attraction), (With<Mob>, Without<Player>)>,
+    mut emitters: Query<
+        (Entity, &mut Emitter, &Transform, &Attraction, Option<&StatusEffects>),
+        (With<Mob>, Without<Player>),
+    >,
 )
 {
     let Ok(player_transform) = player.get_single() else { return };
     let time = clock.elapsed;
 
-    for (entity, mut emitter, transform, attraction) in emitters.iter_mut() {
+    for (entity, mut emitter, transform, attraction, maybe_effects) in emitters.iter_mut() {
         // Wait for emitters to stop moving.
         if !attraction.is_stopped() {
             continue;
         }
 
+        // Stunned emitters can't fire.
+        if maybe_effects.map(|e| e.is_stunned()).unwrap_or(false) {
+            continue;
+        }
+
         // Update emitter cooldown.
         if !emitter.update_cooldown(time) {
             continue;
@@ -53,6 +62,85 @@ fn update_emitter_mobs(
 
 //-------------------------------------------------------------------------------------------------------------------
 
+pub(crate) fn update_spawner_mobs(
+    mut c: Commands,
+    clock: Res<GameClock>,
+    constants: ReactRes<GameConstants>,
+    animations: Res<SpriteAnimations>,
+    mob_data: Res<MobDatabase>,
+    multipliers: Res<MobStatMultipliers>,
+    mut rng: ResMut<GameRng>,
+    player: Query<Entity, With<Player>>,
+    mut spawners: Query<(&mut Spawner, &Transform, &InSpawnEvent, Option<&StatusEffects>), With<Mob>>,
+    alive: Query<(), With<Mob>>,
+)
+{
+    let Ok(player_entity) = player.get_single() else { return };
+    let time = clock.elapsed;
+
+    for (mut spawner, transform, in_event, maybe_effects) in spawners.iter_mut() {
+        // Forget spawned mobs that died.
+        for entry in spawner.entries.iter_mut() {
+            entry.spawned.retain(|entity| alive.contains(*entity));
+        }
+
+        // Stunned spawners can't spawn.
+        if maybe_effects.map(|e| e.is_stunned()).unwrap_or(false) {
+            continue;
+        }
+
+        let spawn_radius = spawner.spawn_radius;
+        let spawner_loc = transform.translation.truncate();
+
+        for entry in spawner.entries.iter_mut() {
+            // Check cooldown.
+            // - The first spawn is delayed relative to when the spawner is first updated.
+            let next_spawn_time = *entry
+                .next_spawn_time
+                .get_or_insert(time + Duration::from_millis(entry.config.initial_delay_ms));
+            if time < next_spawn_time {
+                continue;
+            }
+            entry.next_spawn_time = Some(time + Duration::from_millis(entry.config.cooldown_ms));
+
+            // Check cap.
+            let count = entry
+                .config
+                .count
+                .min(entry.config.max_alive.saturating_sub(entry.spawned.len()));
+            if count == 0 {
+                continue;
+            }
+
+            let Some(data) = mob_data.get(&entry.config.mob) else {
+                tracing::error!("failed accessing mob data for {:?}, skipping spawner spawn", entry.config.mob);
+                continue;
+            };
+
+            // Spawn around the spawner.
+            for _ in 0..count {
+                let direction = rng.rng().gen_range((0.)..TAU);
+                let location = spawner_loc + Vec2::from_angle(direction) * spawn_radius;
+                let entity = data.spawn(
+                    &mut c,
+                    &entry.config.mob,
+                    rng.rng(),
+                    &constants,
+                    Transform::from_translation(location.extend(0.)),
+                    player_entity,
+                    &animations,
+                    in_event.0,
+                    &multipliers,
+                    None,
+                );
+                entry.spawned.push(entity);
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// On death, try to apply damage to the player. We do this manually so the damage is applied
 /// immediately instead of e.g. indirecting through a spawned exploder projectile.
 fn handle_exploder_deaths(
@@ -89,7 +177,70 @@ fn handle_exploder_deaths(
         }
 
         // Send damage event.
-        dmg_events.send(DamageEvent { source: mob_entity, target: player, damage: base_damage });
+        dmg_events.send(DamageEvent {
+            source: mob_entity,
+            target: player,
+            damage: base_damage,
+            max_damage: None,
+            ability: None,
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// On death, spawn child mobs where the mob died.
+///
+/// Children are added to the parent's spawn event so spawn event accounting stays correct.
+fn handle_splitter_deaths(
+    mut c: Commands,
+    mut deaths: EventReader<EntityDeath>,
+    constants: ReactRes<GameConstants>,
+    animations: Res<SpriteAnimations>,
+    mob_data: Res<MobDatabase>,
+    multipliers: Res<MobStatMultipliers>,
+    mut rng: ResMut<GameRng>,
+    splitters: Query<(&Splitter, &Transform, &InSpawnEvent)>,
+    player: Query<Entity, With<Player>>,
+)
+{
+    let Ok(player_entity) = player.get_single() else { return };
+
+    for (splitter, transform, in_event) in deaths
+        .read()
+        .filter_map(|death| splitters.get(**death).ok())
+    {
+        let Some(data) = mob_data.get(&splitter.mob) else {
+            tracing::error!("failed accessing mob data for {:?}, skipping split", splitter.mob);
+            continue;
+        };
+
+        let location = transform.translation.truncate();
+        for _ in 0..splitter.count {
+            // Select random nearby location to spawn the child.
+            let spread = splitter.spread;
+            let offset = match spread > 0. {
+                true => Vec2 {
+                    x: rng.rng().gen_range(-spread..spread),
+                    y: rng.rng().gen_range(-spread..spread),
+                }
+                .clamp_length_max(spread),
+                false => Vec2::ZERO,
+            };
+
+            data.spawn(
+                &mut c,
+                &splitter.mob,
+                rng.rng(),
+                &constants,
+                Transform::from_translation((location + offset).extend(0.)),
+                player_entity,
+                &animations,
+                in_event.0,
+                &multipliers,
+                None,
+            );
+        }
     }
 }
 
@@ -98,11 +249,23 @@ fn handle_exploder_deaths(
 fn apply_collider_effect_impl(
     In((source, target)): In<(Entity, Entity)>,
     mut events: EventWriter<DamageEvent>,
-    colliders: Query<&Collider>,
+    mut status_events: EventWriter<StatusEffectEvent>,
+    colliders: Query<(&Collider, &Transform, Option<&AbilitySource>)>,
 )
 {
-    let Ok(collider) = colliders.get(source) else { return };
-    events.send(DamageEvent { source, target, damage: collider.damage });
+    let Ok((collider, transform, ability)) = colliders.get(source) else { return };
+    events.send(DamageEvent {
+        source,
+        target,
+        damage: collider.damage,
+        max_damage: collider.max_damage,
+        ability: ability.map(|a| a.0.clone()),
+    });
+
+    let origin = transform.translation.truncate();
+    for effect in collider.status_effects.iter() {
+        status_events.send(StatusEffectEvent { source, target, effect: *effect, origin });
+    }
 }
 
 pub fn apply_collider_effect(collider: Entity, target: Entity, c: &mut Commands)
@@ -115,6 +278,9 @@ pub fn apply_collider_effect(collider: Entity, target: Entity, c: &mut Commands)
 pub struct Collider
 {
     pub damage: usize,
+    /// See [`DamageEvent::max_damage`].
+    pub max_damage: Option<usize>,
+    pub status_effects: Vec<StatusEffect>,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -157,6 +323,38 @@ impl Emitter
 
 //-------------------------------------------------------------------------------------------------------------------
 
+#[derive(Debug)]
+struct SpawnerEntryState
+{
+    config: SpawnerEntry,
+    next_spawn_time: Option<Duration>,
+    spawned: Vec<Entity>,
+}
+
+/// Component for spawner mobs.
+#[derive(Component, Debug)]
+pub struct Spawner
+{
+    spawn_radius: f32,
+    entries: Vec<SpawnerEntryState>,
+}
+
+impl Spawner
+{
+    pub fn new(spawn_radius: f32, entries: Vec<SpawnerEntry>) -> Self
+    {
+        Self {
+            spawn_radius,
+            entries: entries
+                .into_iter()
+                .map(|config| SpawnerEntryState { config, next_spawn_time: None, spawned: Vec::default() })
+                .collect(),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Component for mobs that explode on death.
 #[derive(Component, Clone, Debug)]
 pub struct Exploder
@@ -166,6 +364,25 @@ pub struct Exploder
     explosion_animation: String,
 }
 
+impl Exploder
+{
+    pub fn new(base_damage: usize, base_range: f32, explosion_animation: String) -> Self
+    {
+        Self { base_damage, base_range, explosion_animation }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component for mobs that split into other mobs on death.
+#[derive(Component, Clone, Debug)]
+pub struct Splitter
+{
+    mob: String,
+    count: usize,
+    spread: f32,
+}
+
 //-------------------------------------------------------------------------------------------------------------------
 
 #[derive(Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -178,6 +395,17 @@ pub enum MobOnDeathType
         /// The animation to display when exploding. The explosion entity auto-despawns when the animation ends.
         explosion_animation: String,
     },
+    /// Spawns `count` mobs from the `MobDatabase` where the mob died.
+    ///
+    /// Child mobs count toward the parent's spawn event and select their own drops.
+    Split
+    {
+        /// Name of the mob in the `MobDatabase`.
+        mob: String,
+        count: usize,
+        /// Max distance from the parent's death location where children are spawned.
+        spread: f32,
+    },
 }
 
 impl Default for MobOnDeathType
@@ -194,6 +422,24 @@ impl Default for MobOnDeathType
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// A type of mob summoned by a spawner mob.
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpawnerEntry
+{
+    /// Name of the mob in the `MobDatabase`.
+    pub mob: String,
+    /// Number of mobs spawned each time the cooldown expires.
+    pub count: usize,
+    pub cooldown_ms: u64,
+    /// Delay before the first spawn after the spawner appears.
+    #[reflect(default)]
+    pub initial_delay_ms: u64,
+    /// Max number of this mob type spawned by the spawner that can be alive at once.
+    pub max_alive: usize,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum MobType
 {
@@ -201,6 +447,9 @@ pub enum MobType
     {
         base_damage: usize,
         base_cooldown_millis: u64,
+        /// Status effects applied to the player on contact.
+        #[reflect(default)]
+        status_effects: Vec<StatusEffect>,
     },
     Emitter
     {
@@ -210,37 +459,66 @@ pub enum MobType
         projectile: ProjectileConfig,
     },
     OnDeath(MobOnDeathType),
-    //todo: Spawner can spawn different mobs with an internal cooldown per mob type
+    Boss(BossConfig),
+    /// Summons other mobs around itself. Summoned mobs count toward the spawner's spawn event.
+    Spawner
+    {
+        spawns: Vec<SpawnerEntry>,
+        /// Distance from the spawner where mobs are spawned.
+        spawn_radius: f32,
+        /// Distance from the player where the spawner stops moving.
+        #[reflect(default)]
+        stop_distance: f32,
+    },
 }
 
 impl MobType
 {
-    //todo: can 'amplify' the mob stats here
     /// Returns the distance from the player where the entity should stop being attracted.
-    pub fn setup_in_entity(&self, constants: &GameConstants, ec: &mut EntityCommands, start_pos: Vec2) -> f32
+    ///
+    /// The mob's damage is scaled by `damage_multiplier`.
+    pub fn setup_in_entity(
+        &self,
+        constants: &GameConstants,
+        ec: &mut EntityCommands,
+        start_pos: Vec2,
+        damage_multiplier: f32,
+    ) -> f32
     {
         match self.clone() {
-            Self::Collider { base_damage, base_cooldown_millis } => {
+            Self::Collider { base_damage, base_cooldown_millis, status_effects } => {
+                let base_damage = scale_stat(base_damage, damage_multiplier);
                 ec.insert((
                     EffectZone::<Player>::new(
                         EffectZoneConfig::ApplyAndRegen { cooldown_ms: base_cooldown_millis },
                         apply_collider_effect,
                     ),
                     PrevLocation(start_pos),
-                    Collider { damage: base_damage },
+                    Collider { damage: base_damage, max_damage: None, status_effects },
                 ));
                 constants.collider_mob_stop_distance
             }
             Self::Emitter { base_cooldown_millis, base_fire_range, projectile } => {
+                let projectile = projectile.with_damage_multiplier(damage_multiplier);
                 ec.insert(Emitter::new(base_cooldown_millis, projectile));
                 base_fire_range
             }
             Self::OnDeath(on_death) => match on_death {
                 MobOnDeathType::Explode { base_damage, base_range, explosion_animation } => {
+                    let base_damage = scale_stat(base_damage, damage_multiplier);
                     ec.insert(Exploder { base_damage, base_range, explosion_animation });
                     0.
                 }
+                MobOnDeathType::Split { mob, count, spread } => {
+                    ec.insert(Splitter { mob, count, spread });
+                    0.
+                }
             },
+            Self::Boss(config) => config.setup_in_entity(constants, ec, start_pos, damage_multiplier),
+            Self::Spawner { spawns, spawn_radius, stop_distance } => {
+                ec.insert(Spawner::new(spawn_radius, spawns));
+                stop_distance
+            }
         }
     }
 }
@@ -249,7 +527,11 @@ impl Default for MobType
 {
     fn default() -> Self
     {
-        Self::Collider { base_damage: 0, base_cooldown_millis: 1000 }
+        Self::Collider {
+            base_damage: 0,
+            base_cooldown_millis: 1000,
+            status_effects: Vec::default(),
+        }
     }
 }
 
@@ -268,6 +550,9 @@ pub struct MobData
     /// [ (probability of drop, drop) ]
     pub drops: Vec<(f32, CollectableDrop)>,
     pub auto_flip_sprite: bool,
+    /// Makes the mob periodically charge at the player.
+    #[reflect(default)]
+    pub charge: Option<ChargeConfig>,
 }
 
 impl MobData
@@ -275,32 +560,50 @@ impl MobData
     pub fn spawn(
         &self,
         c: &mut Commands,
+        name: &str,
         rng: &mut ChaCha8Rng,
         constants: &GameConstants,
         entity_transform: Transform,
         player_entity: Entity,
         animations: &SpriteAnimations,
         event_id: usize,
-    )
+        multipliers: &MobStatMultipliers,
+        elite: Option<&EliteRoll>,
+    ) -> Entity
     {
         let offset = constants.mob_attraction_offset;
         let target_offset =
             Vec2::new(rng.gen_range(-offset..=offset), rng.gen_range(-offset..=offset)).clamp_length_max(offset);
 
+        // Apply difficulty scaling and elite modifiers.
+        let (health_multiplier, armor_multiplier, speed_multiplier, scale) = elite
+            .map(|e| (e.health_multiplier, e.armor_multiplier, e.speed_multiplier, e.scale))
+            .unwrap_or((1., 1., 1., 1.));
+        let health = scale_stat(self.base_health, multipliers.health * health_multiplier);
+        let armor = scale_stat(self.base_armor, multipliers.armor * armor_multiplier);
+        let speed = self.base_speed_tps * multipliers.speed * speed_multiplier;
+        let mut entity_transform = entity_transform;
+        entity_transform.scale *= scale;
+
         let mut ec = c.spawn_empty();
-        let stop_distance =
-            self.mob_type
-                .setup_in_entity(constants, &mut ec, entity_transform.translation.truncate());
+        let stop_distance = self.mob_type.setup_in_entity(
+            constants,
+            &mut ec,
+            entity_transform.translation.truncate(),
+            multipliers.damage,
+        );
         ec.insert((
             Mob,
             SpatialBundle::from_transform(entity_transform),
             SpriteLayer::Objects,
-            AabbSize(self.hitbox),
-            Health::new(self.base_health),
-            Armor::new(self.base_armor),
+            AabbSize(self.hitbox * scale),
+            Health::new(health.max(1)),
+            Armor::new(armor),
+            StatusEffects::default(),
+            SpriteTint::default(),
             Attraction::new(
                 player_entity,
-                self.base_speed_tps,
+                speed,
                 0.,
                 target_offset,
                 stop_distance,
@@ -310,12 +613,39 @@ impl MobData
             StateScoped(GameState::Play),
             BoundInMap,
             InSpawnEvent(event_id),
-        ))
-        .set_sprite_animation(&animations, &self.animation);
+            MobSpawnInfo {
+                name: name.into(),
+                multipliers: *multipliers,
+                elite: elite.cloned(),
+            },
+        ));
 
-        if let Some(drop) = self.select_collectable_drop(rng) {
+        if let Some(elite) = elite {
+            elite.setup_in_entity(&mut ec, multipliers.damage);
+        }
+        ec.set_sprite_animation(&animations, &self.animation);
+
+        let mut drop = self.select_collectable_drop(rng);
+        for _ in 0..elite.map(|e| e.extra_drop_rolls).unwrap_or(0) {
+            let Some(extra) = self.select_collectable_drop(rng) else { continue };
+            match &mut drop {
+                Some(drop) => drop.extend(extra),
+                None => drop = Some(extra),
+            }
+        }
+        if let Some(drop) = drop {
             ec.insert(drop);
         }
+
+        if let Some(charge) = &self.charge {
+            // Charges are fast, so make sure collisions are swept.
+            ec.insert((
+                Charger::new(charge.clone()),
+                PrevLocation(entity_transform.translation.truncate()),
+            ));
+        }
+
+        ec.id()
     }
 
     fn select_collectable_drop(&self, rng: &mut ChaCha8Rng) -> Option<CollectableDrop>
@@ -355,6 +685,19 @@ pub struct Mob;
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Records how a mob was spawned so it can be spawned again (e.g. when resuming a day).
+#[derive(Component, Debug, Clone)]
+pub struct MobSpawnInfo
+{
+    /// Name of the mob in the `MobDatabase`.
+    pub name: String,
+    /// Difficulty multipliers when the mob spawned.
+    pub multipliers: MobStatMultipliers,
+    pub elite: Option<EliteRoll>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(SystemSet, Hash, Eq, PartialEq, Debug, Copy, Clone)]
 pub struct MobUpdateSet;
 
@@ -368,8 +711,15 @@ impl Plugin for MobPlugin
     {
         app.register_command::<MobDatabase>()
             .init_resource::<MobDatabase>()
-            .add_systems(Update, update_emitter_mobs.in_set(MobUpdateSet))
-            .add_systems(Update, handle_exploder_deaths.in_set(DamageSet::HandleDeaths));
+            .add_systems(Update, (update_emitter_mobs, update_spawner_mobs).in_set(MobUpdateSet))
+            .add_systems(Update, handle_exploder_deaths.in_set(DamageSet::HandleDeaths))
+            // Both systems use `GameRng`, so they must be ordered for determinism.
+            .add_systems(
+                Update,
+                handle_splitter_deaths
+                    .after(handle_collectable_drops)
+                    .in_set(DamageSet::HandleDeaths),
+            );
     }
 }
 
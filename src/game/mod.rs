@@ -1,43 +1,73 @@
 mod animations;
 mod attraction;
 mod billboard_cache;
+mod boss;
 mod camera;
+mod charger;
 mod collectables;
 mod damage;
+mod damage_numbers;
 mod day_end;
+mod day_snapshot;
+mod difficulty;
 mod effect;
+mod elite;
 mod game_clock;
+mod hazards;
 mod intersections;
 mod light;
 mod map;
+mod map_generation;
 mod mob;
+mod obstacles;
 mod player;
 mod plugin;
 mod power_up;
 mod projectiles;
+mod props;
+mod run_stats;
+mod spatial_grid;
 mod spawning;
 mod sprite_layers;
+mod sprite_tint;
 mod stats;
+mod status_effects;
+mod target_query;
 mod ui;
 
 pub use animations::*;
 pub use attraction::*;
 pub use billboard_cache::*;
+pub use boss::*;
 pub use camera::*;
+pub use charger::*;
 pub use collectables::*;
 pub use damage::*;
+pub use damage_numbers::*;
 pub use day_end::*;
+pub use day_snapshot::*;
+pub use difficulty::*;
 pub use effect::*;
+pub use elite::*;
 pub use game_clock::*;
+pub use hazards::*;
 pub use intersections::*;
 pub use light::*;
 pub use map::*;
+pub use map_generation::*;
 pub use mob::*;
+pub use obstacles::*;
 pub use player::*;
 pub use plugin::*;
 pub use power_up::*;
 pub use projectiles::*;
+pub use props::*;
+pub use run_stats::*;
+pub use spatial_grid::*;
 pub use spawning::*;
 pub use sprite_layers::*;
+pub use sprite_tint::*;
 pub use stats::*;
+pub use status_effects::*;
+pub use target_query::*;
 pub use ui::*;
@@ -0,0 +1,520 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use bevy::ecs::world::Command;
+use bevy::math::bounding::{Aabb2d, BoundingVolume, IntersectsVolume};
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Max number of attempts to find a free location for each randomly-placed obstacle.
+const MAX_PLACEMENT_ATTEMPTS: usize = 20;
+
+/// Flow field cost of moving to an orthogonal neighbor cell.
+const STRAIGHT_COST: u32 = 10;
+/// Flow field cost of moving to a diagonal neighbor cell.
+const DIAGONAL_COST: u32 = 14;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn spawn_obstacles(
+    mut c: Commands,
+    mut rng: ResMut<GameRng>,
+    constants: ReactRes<GameConstants>,
+    images: Res<ImageMap>,
+    database: Res<ObstacleDatabase>,
+    layout: Res<ObstacleLayout>,
+)
+{
+    let rng = rng.rng();
+    let map_half_size = map_area_half_size(&constants);
+    let mut placed: Vec<Aabb2d> = Vec::default();
+
+    let spawn = |c: &mut Commands, name: &String, location: Vec2| -> Option<Aabb2d> {
+        let Some(data) = database.get(name) else {
+            tracing::error!("failed accessing obstacle data for {:?}, skipping obstacle", name);
+            return None;
+        };
+        let hitbox = data.hitbox.unwrap_or(data.size);
+        c.spawn((
+            Obstacle { blocks_projectiles: data.blocks_projectiles },
+            SpriteBundle {
+                texture: images.get(&data.image),
+                sprite: Sprite { custom_size: Some(data.size), ..default() },
+                transform: Transform::from_translation(location.extend(0.)),
+                ..default()
+            },
+            AabbSize(hitbox),
+            SpriteLayer::Objects,
+            StateScoped(GameState::Play),
+        ));
+        Some(Aabb2d::new(location, hitbox / 2.))
+    };
+
+    // Place obstacles from the layout.
+    for placement in layout.fixed.iter() {
+        if let Some(aabb) = spawn(&mut c, &placement.obstacle, placement.location) {
+            placed.push(aabb);
+        }
+    }
+
+    // Place random obstacles.
+    // - Obstacles are kept away from the player's spawn point and from each other.
+    for (count, name) in layout.random.iter() {
+        let Some(data) = database.get(name) else {
+            tracing::error!("failed accessing obstacle data for {:?}, skipping obstacle", name);
+            continue;
+        };
+        let half_size = data.hitbox.unwrap_or(data.size) / 2.;
+        let bounds = (map_half_size - half_size).max(Vec2::ZERO);
+
+        for _ in 0..*count {
+            for _ in 0..MAX_PLACEMENT_ATTEMPTS {
+                let location = Vec2 {
+                    x: rng.gen_range(-bounds.x..=bounds.x),
+                    y: rng.gen_range(-bounds.y..=bounds.y),
+                };
+                let aabb = Aabb2d::new(location, half_size);
+                if aabb.closest_point(Vec2::ZERO).length() < layout.clear_radius {
+                    continue;
+                }
+                let spaced = aabb.grow(Vec2::splat(layout.spacing / 2.));
+                if placed.iter().any(|other| other.intersects(&spaced)) {
+                    continue;
+                }
+
+                if let Some(aabb) = spawn(&mut c, name, location) {
+                    placed.push(aabb);
+                }
+                break;
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Pushes entities that are bound in the map out of obstacles.
+fn resolve_obstacle_collisions(
+    grid: Res<SpatialGrid>,
+    mut movers: Query<(&mut Transform, &AabbSize), (With<BoundInMap>, Without<Obstacle>)>,
+    obstacles: Query<(&Transform, &AabbSize), With<Obstacle>>,
+)
+{
+    if obstacles.is_empty() {
+        return;
+    }
+
+    for (mut transform, size) in movers.iter_mut() {
+        let mut aabb = size.get_2d(&transform);
+        let mut correction = Vec2::ZERO;
+
+        grid.for_each_in_aabb(aabb, |entity| {
+            let Ok((obstacle_transform, obstacle_size)) = obstacles.get(entity) else { return };
+            let obstacle_aabb = obstacle_size.get_2d(obstacle_transform);
+            let overlap = aabb.max.min(obstacle_aabb.max) - aabb.min.max(obstacle_aabb.min);
+            if overlap.x <= 0. || overlap.y <= 0. {
+                return;
+            }
+
+            // Push out along the axis with the least overlap.
+            let away = aabb.center() - obstacle_aabb.center();
+            let push = if overlap.x < overlap.y {
+                Vec2::new(overlap.x.copysign(away.x), 0.)
+            } else {
+                Vec2::new(0., overlap.y.copysign(away.y))
+            };
+            aabb = Aabb2d::new(aabb.center() + push, aabb.half_size());
+            correction += push;
+        });
+
+        if correction != Vec2::ZERO {
+            transform.translation += correction.extend(0.);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Despawns projectiles that hit obstacles with `blocks_projectiles` set.
+fn block_projectiles_with_obstacles(
+    mut c: Commands,
+    grid: Res<SpatialGrid>,
+    projectiles: Query<(Entity, &Transform, &AabbSize), With<Projectile>>,
+    obstacles: Query<(&Transform, &AabbSize, &Obstacle)>,
+)
+{
+    if obstacles.is_empty() {
+        return;
+    }
+
+    for (entity, transform, size) in projectiles.iter() {
+        let aabb = size.get_2d(transform);
+        let mut blocked = false;
+        grid.for_each_in_aabb(aabb, |other| {
+            let Ok((obstacle_transform, obstacle_size, obstacle)) = obstacles.get(other) else { return };
+            blocked |= obstacle.blocks_projectiles && obstacle_size.get_2d(obstacle_transform).intersects(&aabb);
+        });
+
+        if blocked {
+            c.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn update_obstacle_flow_field(
+    mut removed: RemovedComponents<Obstacle>,
+    constants: ReactRes<GameConstants>,
+    mut field: ResMut<ObstacleFlowField>,
+    added: Query<(), Added<Obstacle>>,
+    obstacles: Query<(&Transform, &AabbSize), With<Obstacle>>,
+    player: Query<&Transform, With<Player>>,
+)
+{
+    // Rebuild blocked cells when obstacles change.
+    let changed = !added.is_empty() || removed.read().count() > 0;
+    if changed || !field.is_initialized() {
+        field.rebuild(
+            &constants,
+            obstacles
+                .iter()
+                .map(|(transform, size)| size.get_2d(transform)),
+        );
+    }
+
+    // Recompute costs when the player moves to a new cell.
+    let Ok(player_transform) = player.get_single() else { return };
+    field.update_target(player_transform.translation.truncate(), changed);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Routes mobs around obstacles when obstacles block the direct path to the player.
+fn update_mob_waypoints(
+    field: Res<ObstacleFlowField>,
+    player: Query<Entity, With<Player>>,
+    mut mobs: Query<(&Transform, &mut Attraction), With<Mob>>,
+)
+{
+    let Ok(player_entity) = player.get_single() else { return };
+
+    for (transform, mut attraction) in mobs.iter_mut() {
+        let waypoint = match attraction.target() == player_entity {
+            true => field.waypoint(transform.translation.truncate()),
+            false => None,
+        };
+        if attraction.waypoint() != waypoint {
+            attraction.set_waypoint(waypoint);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn reset_obstacle_flow_field(mut field: ResMut<ObstacleFlowField>)
+{
+    *field = ObstacleFlowField::default();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component for static obstacles that block movement.
+#[derive(Component, Debug)]
+pub struct Obstacle
+{
+    blocks_projectiles: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Grid of path costs to the player, used to route mobs around obstacles.
+///
+/// Cells cover the map area. Cells overlapping obstacles (padded by `GameConstants::obstacle_path_padding`) are
+/// blocked.
+#[derive(Resource, Default, Debug)]
+pub struct ObstacleFlowField
+{
+    cell_size: f32,
+    /// Lower-left corner of the grid.
+    origin: Vec2,
+    dims: UVec2,
+    blocked: Vec<bool>,
+    has_obstacles: bool,
+
+    target: Option<UVec2>,
+    costs: Vec<u32>,
+    heap: BinaryHeap<Reverse<(u32, usize)>>,
+}
+
+impl ObstacleFlowField
+{
+    fn is_initialized(&self) -> bool
+    {
+        self.dims != UVec2::ZERO
+    }
+
+    fn rebuild(&mut self, constants: &GameConstants, obstacles: impl Iterator<Item = Aabb2d>)
+    {
+        let map_size = map_area_size(constants);
+        self.cell_size = constants.obstacle_path_cell_size.max(1.);
+        self.origin = -map_size / 2.;
+        self.dims = (map_size / self.cell_size).ceil().max(Vec2::ONE).as_uvec2();
+        self.blocked.clear();
+        self.blocked
+            .resize((self.dims.x * self.dims.y) as usize, false);
+        self.has_obstacles = false;
+        self.target = None;
+
+        let padding = Vec2::splat(constants.obstacle_path_padding.max(0.));
+        for obstacle in obstacles {
+            self.has_obstacles = true;
+            let Some(min) = self.cell(obstacle.min - padding) else { continue };
+            let Some(max) = self.cell(obstacle.max + padding) else { continue };
+            for x in min.x..=max.x {
+                for y in min.y..=max.y {
+                    let index = self.index(UVec2 { x, y });
+                    self.blocked[index] = true;
+                }
+            }
+        }
+    }
+
+    /// Recomputes path costs if the target moved to a new cell.
+    fn update_target(&mut self, target: Vec2, force: bool)
+    {
+        if !self.has_obstacles {
+            return;
+        }
+        let Some(target_cell) = self.cell(target) else { return };
+        if !force && self.target == Some(target_cell) {
+            return;
+        }
+        self.target = Some(target_cell);
+
+        // Dijkstra from the target cell.
+        self.costs.clear();
+        self.costs.resize(self.blocked.len(), u32::MAX);
+        self.heap.clear();
+        let start = self.index(target_cell);
+        self.costs[start] = 0;
+        self.heap.push(Reverse((0, start)));
+
+        while let Some(Reverse((cost, index))) = self.heap.pop() {
+            if cost > self.costs[index] {
+                continue;
+            }
+            let cell = self.cell_from_index(index);
+            let neighbors: SmallVec<[(UVec2, u32); 8]> = self.neighbors(cell).collect();
+            for (neighbor, step_cost) in neighbors {
+                let neighbor_index = self.index(neighbor);
+                let next_cost = cost + step_cost;
+                if next_cost >= self.costs[neighbor_index] {
+                    continue;
+                }
+                self.costs[neighbor_index] = next_cost;
+                self.heap.push(Reverse((next_cost, neighbor_index)));
+            }
+        }
+    }
+
+    /// Returns the location an entity at `location` should move toward to reach the target, or `None` if the
+    /// entity can move directly toward the target.
+    fn waypoint(&self, location: Vec2) -> Option<Vec2>
+    {
+        if !self.has_obstacles {
+            return None;
+        }
+        let target = self.target?;
+        let cell = self.cell(location)?;
+        let cost = *self.costs.get(self.index(cell))?;
+        if cost == u32::MAX {
+            return None;
+        }
+
+        // Only follow the field when obstacles make the path noticeably longer than the direct path.
+        let delta = (cell.as_ivec2() - target.as_ivec2()).abs();
+        let direct_cost = STRAIGHT_COST * (delta.x.max(delta.y) - delta.x.min(delta.y)) as u32
+            + DIAGONAL_COST * delta.x.min(delta.y) as u32;
+        if cost <= direct_cost + STRAIGHT_COST {
+            return None;
+        }
+
+        // Move toward the neighbor closest to the target.
+        self.neighbors(cell)
+            .map(|(neighbor, _)| (neighbor, self.costs[self.index(neighbor)]))
+            .filter(|(_, neighbor_cost)| *neighbor_cost < cost)
+            .min_by_key(|(_, neighbor_cost)| *neighbor_cost)
+            .map(|(neighbor, _)| self.center(neighbor))
+    }
+
+    /// Iterates unblocked neighbors of a cell. Diagonal moves can't cut the corners of blocked cells.
+    fn neighbors(&self, cell: UVec2) -> impl Iterator<Item = (UVec2, u32)> + '_
+    {
+        let cell = cell.as_ivec2();
+        let open = move |x: i32, y: i32| -> Option<UVec2> {
+            if x < 0 || y < 0 || x >= self.dims.x as i32 || y >= self.dims.y as i32 {
+                return None;
+            }
+            let neighbor = UVec2 { x: x as u32, y: y as u32 };
+            (!self.blocked[self.index(neighbor)]).then_some(neighbor)
+        };
+
+        [(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (-1, 1), (1, -1), (1, 1)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| {
+                let neighbor = open(cell.x + dx, cell.y + dy)?;
+                if dx == 0 || dy == 0 {
+                    return Some((neighbor, STRAIGHT_COST));
+                }
+                open(cell.x + dx, cell.y)?;
+                open(cell.x, cell.y + dy)?;
+                Some((neighbor, DIAGONAL_COST))
+            })
+    }
+
+    fn cell(&self, location: Vec2) -> Option<UVec2>
+    {
+        let cell = ((location - self.origin) / self.cell_size)
+            .floor()
+            .as_ivec2();
+        if cell.x < 0 || cell.y < 0 {
+            return None;
+        }
+        let cell = cell.as_uvec2();
+        (cell.x < self.dims.x && cell.y < self.dims.y).then_some(cell)
+    }
+
+    fn index(&self, cell: UVec2) -> usize
+    {
+        (cell.y * self.dims.x + cell.x) as usize
+    }
+
+    fn cell_from_index(&self, index: usize) -> UVec2
+    {
+        let index = index as u32;
+        UVec2 { x: index % self.dims.x, y: index / self.dims.x }
+    }
+
+    fn center(&self, cell: UVec2) -> Vec2
+    {
+        self.origin + (cell.as_vec2() + Vec2::splat(0.5)) * self.cell_size
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObstacleData
+{
+    /// Image from the `ImageMap`.
+    pub image: String,
+    /// Size of the displayed image.
+    pub size: Vec2,
+    /// Defaults to `size`.
+    #[reflect(default)]
+    pub hitbox: Option<Vec2>,
+    #[reflect(default)]
+    pub blocks_projectiles: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// [obstacle name, obstacle data]
+#[derive(Resource, Deref, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObstacleDatabase(HashMap<String, ObstacleData>);
+
+impl Command for ObstacleDatabase
+{
+    fn apply(self, w: &mut World)
+    {
+        w.insert_resource(self);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObstaclePlacement
+{
+    /// Name of the obstacle in the `ObstacleDatabase`.
+    pub obstacle: String,
+    pub location: Vec2,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Obstacles placed on the map at the start of each day.
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObstacleLayout
+{
+    /// Obstacles placed at fixed locations.
+    #[reflect(default)]
+    pub fixed: Vec<ObstaclePlacement>,
+    /// [ (count, obstacle name) ]
+    ///
+    /// Obstacles placed at random locations. Obstacles that can't find a free location are skipped.
+    #[reflect(default)]
+    pub random: Vec<(usize, String)>,
+    /// Random obstacles won't be placed within this distance of the center of the map, where the player spawns.
+    #[reflect(default)]
+    pub clear_radius: f32,
+    /// Min distance between random obstacles and other obstacles.
+    #[reflect(default)]
+    pub spacing: f32,
+}
+
+impl Command for ObstacleLayout
+{
+    fn apply(self, w: &mut World)
+    {
+        w.insert_resource(self);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct ObstaclesPlugin;
+
+impl Plugin for ObstaclesPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_command::<ObstacleDatabase>()
+            .register_command::<ObstacleLayout>()
+            .init_resource::<ObstacleDatabase>()
+            .init_resource::<ObstacleLayout>()
+            .init_resource::<ObstacleFlowField>()
+            // Obstacles use `GameRng`, so they must be placed after the day's rng state is recorded or restored.
+            .add_systems(OnEnter(PlayState::Day), spawn_obstacles.after(DayRngSetupSet))
+            .add_systems(OnExit(GameState::Play), reset_obstacle_flow_field)
+            .add_systems(
+                Update,
+                (update_obstacle_flow_field, update_mob_waypoints)
+                    .chain()
+                    .in_set(MobUpdateSet),
+            )
+            .add_systems(
+                Update,
+                block_projectiles_with_obstacles
+                    .after(update_projectile_transforms)
+                    .in_set(ProjectileUpdateSet),
+            )
+            .add_systems(
+                Update,
+                resolve_obstacle_collisions
+                    .before(force_in_map_bounds)
+                    .in_set(MapConstraintsSet),
+            );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
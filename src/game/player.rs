@@ -209,15 +209,15 @@ fn update_player_state_from_input(
 
 fn update_player_transform_from_tick(
     time: Res<Time>,
-    mut player: Query<(&mut Transform, &MoveSpeed, &PlayerDirection, &Action), With<Player>>,
+    mut player: Query<(&mut Transform, &MoveSpeed, &StatusEffects, &PlayerDirection, &Action), With<Player>>,
 )
 {
-    let (mut player_transform, speed, direction, action) = player.single_mut();
+    let (mut player_transform, speed, effects, direction, action) = player.single_mut();
     let delta = time.delta();
 
     let translation_magnitude = match *action {
         Action::Standing => 0.,
-        Action::Running => (speed.current() as f32) * delta.as_secs_f32(),
+        Action::Running => (speed.current() as f32) * effects.speed_multiplier() * delta.as_secs_f32(),
     };
 
     let translation_direction = direction.to_unit_vector();
@@ -317,23 +317,41 @@ fn spawn_player(
     animations: Res<SpriteAnimations>,
     billboard_cache: Res<BillboardCache>,
     fonts: Res<FontMap>,
+    karma_shop: Res<KarmaShop>,
+    karma_upgrades: ReactRes<KarmaUpgrades>,
 )
 {
+    // Purchased karma upgrades are applied as base stats, since passives control stat bonuses.
+    let karma = |passive| karma_shop.stat_bonus(passive, &karma_upgrades);
+
     let mut billboard_entities = BillboardEntities::default();
     c.spawn((
         Player,
         (
-            Health::new(constants.player_base_hp),
-            HealthRegen::new(0),
-            Armor::new(constants.player_base_armor),
-            CooldownReduction::new(0),
-            MoveSpeed::new(constants.player_run_speed_tps),
+            Health::new(constants.player_base_hp + karma(Passive::Health)),
+            HealthRegen::new(karma(Passive::HealthRegen)),
+            Armor::new(constants.player_base_armor + karma(Passive::Armor)),
+            CooldownReduction::new(karma(Passive::CooldownReduction)),
+            MoveSpeed::new(constants.player_run_speed_tps + karma(Passive::MoveSpeed)),
             CollectionRange::new(constants.hoover_detection_range),
-            AreaSize::new(1.0),
-            DamageAmp::new(0),
-            ExpAmp::new(0),
+            AreaSize::new(1.0 + (karma(Passive::AreaSize) as f32) / 100.),
+            DamageAmp::new(karma(Passive::DamageAmp)),
+            CritChance::new(constants.player_base_crit_chance + karma(Passive::CritChance)),
+            CritDamage::new(constants.player_base_crit_damage + karma(Passive::CritDamage)),
+            ExpAmp::new(karma(Passive::ExpAmp)),
             Level::new(constants.player_exp_start, constants.player_exp_rate),
         ),
+        StatusEffects::default(),
+        SpriteTint::default(),
+        Invulnerability::new(
+            constants.player_invulnerability_ms,
+            constants.player_invulnerability_blink_ms,
+            constants.player_invulnerability_blink_alpha,
+        ),
+        HitKnockback(StatusEffect::Knockback {
+            distance: constants.player_hit_knockback_distance,
+            duration_ms: constants.player_hit_knockback_ms,
+        }),
         SpatialBundle::from_transform(Transform::default()),
         SpriteLayer::Objects,
         PlayerDirection::Up,
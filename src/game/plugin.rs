@@ -14,17 +14,32 @@ impl Plugin for GamePlugin
             .add_plugins(BillboardCachePlugin)
             .add_plugins(DayEndPlugin)
             .add_plugins(SpriteLayersPlugin)
+            .add_plugins(SpriteTintPlugin)
             .add_plugins(MapPlugin)
+            .add_plugins(MapGenerationPlugin)
+            .add_plugins(ObstaclesPlugin)
+            .add_plugins(HazardsPlugin)
+            .add_plugins(PropsPlugin)
             .add_plugins(MobPlugin)
+            .add_plugins(BossPlugin)
+            .add_plugins(ChargerPlugin)
+            .add_plugins(ElitePlugin)
             .add_plugins(PlayerPlugin)
             .add_plugins(CollectablesPlugin)
             .add_plugins(IntersectionsPlugin)
+            .add_plugins(SpatialGridPlugin)
+            .add_plugins(TargetQueryPlugin)
             .add_plugins(AttractionPlugin)
             .add_plugins(DamagePlugin)
+            .add_plugins(DamageNumbersPlugin)
             .add_plugins(PowerUpPlugin)
             .add_plugins(SpawningPlugin)
+            .add_plugins(DaySnapshotPlugin)
+            .add_plugins(DifficultyPlugin)
             .add_plugins(ProjectilePlugin)
             .add_plugins(StatsPlugin)
+            .add_plugins(RunStatsPlugin)
+            .add_plugins(StatusEffectsPlugin)
             .add_plugins(GameUiPlugin)
             .add_plugins(GameClockPlugin)
             .add_plugins(GameCameraPlugin)
@@ -51,7 +66,8 @@ impl Plugin for GamePlugin
                     .run_if(in_state(PlayState::Day)),
             )
             .add_effect_target::<Player>()
-            .add_effect_target::<Mob>();
+            .add_effect_target::<Mob>()
+            .add_effect_target::<Prop>();
     }
 }
 
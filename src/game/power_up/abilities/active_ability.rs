@@ -0,0 +1,559 @@
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Gets the value for a given level, or the last value if the level is too high.
+fn get_for_level<T: Copy + Default>(values: &[T], level: usize) -> T
+{
+    let level = (level.saturating_sub(1)).min(values.len().saturating_sub(1));
+    values.get(level).cloned().unwrap_or_default()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_ability_damage_impl(
+    In((effect, target)): In<(Entity, Entity)>,
+    mut events: EventWriter<DamageEvent>,
+    mut status_events: EventWriter<StatusEffectEvent>,
+    damage: Query<(&AbilityDamage, &Transform, Option<&AbilitySource>)>,
+)
+{
+    let Ok((damage, transform, ability)) = damage.get(effect) else { return };
+    events.send(DamageEvent {
+        source: damage.source,
+        target,
+        damage: damage.damage,
+        max_damage: damage.max_damage,
+        ability: ability.map(|a| a.0.clone()),
+    });
+
+    let origin = transform.translation.truncate();
+    for effect in damage.status_effects.iter() {
+        status_events.send(StatusEffectEvent { source: damage.source, target, effect: *effect, origin });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_ability_damage(effect: Entity, target: Entity, c: &mut Commands)
+{
+    c.syscall((effect, target), apply_ability_damage_impl);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_thrown_projectile_landing_impl(
+    In((projectile, target)): In<(Entity, Entity)>,
+    mut c: Commands,
+    animations: Res<SpriteAnimations>,
+    thrown: Query<(&Transform, &ThrownProjectile, Option<&AbilitySource>)>,
+)
+{
+    let Ok((transform, thrown, ability)) = thrown.get(projectile) else { return };
+
+    // Clean up landing target and self.
+    c.entity(target).despawn_recursive();
+    c.entity(projectile).despawn_recursive();
+
+    // Spawn damaging effect.
+    let mut ec = c.spawn((
+        SpatialBundle::from_transform(*transform), //note: adopts sprite scaling from projectile
+        StateScoped(GameState::Play),
+        DespawnOnAnimationCycle,
+        thrown.effect_sprite_layer,
+        EffectZone::<Mob>::new(
+            // Use regen so the effect isn't despawned. We want it to despawn after the animation.
+            EffectZoneConfig::ApplyAndRegen { cooldown_ms: 1_000_000 },
+            apply_ability_damage,
+        ),
+        EffectZone::<Prop>::new(
+            EffectZoneConfig::ApplyAndRegen { cooldown_ms: 1_000_000 },
+            apply_ability_damage,
+        ),
+        AbilityDamage {
+            source: thrown.source,
+            damage: thrown.damage,
+            max_damage: thrown.max_damage,
+            status_effects: thrown.status_effects.clone(),
+        },
+        AabbSize(thrown.area),
+    ));
+    if let Some(ability) = ability {
+        ec.insert(ability.clone());
+    }
+    ec.set_sprite_animation(&animations, &thrown.effect_animation);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn apply_thrown_projectile_landing(projectile: Entity, target: Entity, c: &mut Commands)
+{
+    c.syscall((projectile, target), apply_thrown_projectile_landing_impl);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Checks if the active ability timers need to be set up on the player.
+fn add_active_ability_timers(mut c: Commands, player: Query<Entity, (With<Player>, Without<ActiveAbilityTimers>)>)
+{
+    let Ok(entity) = player.get_single() else { return };
+    c.entity(entity).try_insert(ActiveAbilityTimers::default());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn update_active_abilities(
+    mut c: Commands,
+    mut rng: ResMut<GameRng>,
+    clock: Res<GameClock>,
+    animations: Res<SpriteAnimations>,
+    mut player: Query<
+        (
+            Entity,
+            &Transform,
+            &CooldownReduction,
+            &AreaSize,
+            &mut ActiveAbilityTimers,
+        ),
+        With<Player>,
+    >,
+    player_powerups: ReactRes<PlayerPowerups>,
+    database: Res<ActiveAbilityDatabase>,
+    targets: TargetQuery,
+)
+{
+    let Ok((player_entity, transform, cdr, area_size, mut timers)) = player.get_single_mut() else { return };
+    let player_loc = transform.translation.truncate();
+    let time = clock.elapsed;
+
+    for config in database.iter() {
+        let level = player_powerups.get(&config.name);
+        if level == 0 {
+            continue;
+        }
+
+        // Check cooldown.
+        if let Some(next_fire_time) = timers.get(&config.name) {
+            if time < *next_fire_time {
+                continue;
+            }
+        }
+
+        // Find where to aim.
+        let aims = config
+            .targeting
+            .get_aims(player_loc, &targets, config.targets_props, rng.rng());
+        if aims.is_empty() {
+            // If no targets, don't fire.
+            continue;
+        }
+
+        // Spawn projectiles.
+        let projectile = config.get_projectile(level);
+        let count = config.get_projectile_count(level);
+        let spread = config.get_spread(level);
+        for aim in aims.iter() {
+            for i in 0..count {
+                // Fan out projectiles evenly over the spread.
+                let offset = match count {
+                    1 => 0.,
+                    _ => -spread / 2. + spread * (i as f32) / ((count - 1) as f32),
+                };
+                let dir = Dir2::new_unchecked(Vec2::from_angle(offset.to_radians()).rotate(*aim.direction));
+
+                match config.delivery {
+                    ProjectileDelivery::Straight => {
+                        let Some(entity) = projectile.create_projectile::<Mob>(
+                            &mut c,
+                            &clock,
+                            &animations,
+                            player_entity,
+                            player_loc + dir.rotation_from_x() * config.launch_offset,
+                            dir,
+                            &area_size,
+                            None,
+                        ) else {
+                            continue;
+                        };
+                        projectile.add_effect_target::<Prop>(&mut c, entity);
+                        c.entity(entity).insert(AbilitySource(config.name.clone()));
+                    }
+                    ProjectileDelivery::Thrown { distance } => {
+                        let distance = aim.distance.unwrap_or(distance);
+                        config.throw_projectile(
+                            &mut c,
+                            &clock,
+                            &animations,
+                            &projectile,
+                            player_entity,
+                            player_loc,
+                            dir,
+                            distance,
+                            &area_size,
+                        );
+                    }
+                }
+            }
+        }
+
+        // Update cooldown.
+        timers.insert(config.name.clone(), time + config.get_cooldown(level, &cdr));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A direction an ability fires in.
+#[derive(Debug, Copy, Clone)]
+struct AbilityAim
+{
+    direction: Dir2,
+    /// Distance to the target, if the ability aimed at a target.
+    distance: Option<f32>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Damage applied by an ability's effect zone.
+#[derive(Component, Debug)]
+struct AbilityDamage
+{
+    source: Entity,
+    damage: usize,
+    max_damage: Option<usize>,
+    status_effects: Vec<StatusEffect>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Effect target for thrown projectiles. The projectile lands when it reaches this entity.
+#[derive(Component, Debug)]
+struct ThrowTarget;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the effect that should be spawned when a thrown projectile lands.
+#[derive(Component, Debug)]
+struct ThrownProjectile
+{
+    source: Entity,
+    damage: usize,
+    max_damage: Option<usize>,
+    area: Vec2,
+    effect_animation: String,
+    effect_sprite_layer: SpriteLayer,
+    status_effects: Vec<StatusEffect>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Next fire time of each active ability, keyed by ability name.
+#[derive(Component, Deref, DerefMut, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveAbilityTimers(HashMap<String, Duration>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// How an active ability decides where to fire.
+#[derive(Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AbilityTargeting
+{
+    /// Fires at mobs selected by a targeting policy. Doesn't fire if no mobs are found.
+    Target(TargetingPolicy),
+    /// Fires in a random direction.
+    RandomDirection,
+    /// Fires in fixed directions, in degrees counter-clockwise from the +x axis.
+    FixedDirections(Vec<f32>),
+}
+
+impl AbilityTargeting
+{
+    fn get_aims(
+        &self,
+        origin: Vec2,
+        targets: &TargetQuery,
+        include_props: bool,
+        rng: &mut impl Rng,
+    ) -> Vec<AbilityAim>
+    {
+        match self {
+            Self::Target(policy) => targets
+                .select(origin, policy, include_props, rng)
+                .iter()
+                .map(|location| {
+                    let delta = *location - origin;
+                    AbilityAim {
+                        direction: Dir2::new(delta).unwrap_or(Dir2::new_unchecked(Vec2::default().with_x(1.))),
+                        distance: Some(delta.length()),
+                    }
+                })
+                .collect(),
+            Self::RandomDirection => {
+                let rotation = rng.gen_range((0.)..TAU);
+                vec![AbilityAim {
+                    direction: Dir2::new_unchecked(Vec2::from_angle(rotation)),
+                    distance: None,
+                }]
+            }
+            Self::FixedDirections(directions) => directions
+                .iter()
+                .map(|degrees| AbilityAim {
+                    direction: Dir2::new_unchecked(Vec2::from_angle(degrees.to_radians())),
+                    distance: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Default for AbilityTargeting
+{
+    fn default() -> Self
+    {
+        Self::RandomDirection
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// How an active ability's projectiles travel.
+#[derive(Reflect, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ProjectileDelivery
+{
+    /// Projectiles fly in a straight line until they hit something or leave the map.
+    #[default]
+    Straight,
+    /// Projectiles are thrown to a location and apply their effect where they land.
+    ///
+    /// The projectile's `velocity_tps` is used as the throw speed, and its `effect_animation` is required. The
+    /// effect's area is taken from `Explosion` and `Pulse` projectile types, otherwise the projectile size is
+    /// used.
+    Thrown
+    {
+        /// Distance to throw when the ability's targeting doesn't select a target location.
+        distance: f32,
+    },
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Reflect, Default, Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ActiveAbilityConfig
+{
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    pub targeting: AbilityTargeting,
+    /// If set, `AbilityTargeting::Target` can select destructible props as well as mobs.
+    ///
+    /// Projectiles damage props they hit regardless of this setting.
+    #[reflect(default)]
+    pub targets_props: bool,
+    #[reflect(default)]
+    pub delivery: ProjectileDelivery,
+    /// Template for the ability's projectiles. The projectile type's damage is replaced by `damage_by_level`.
+    pub projectile: ProjectileConfig,
+    pub damage_by_level: Vec<usize>,
+    /// Optional upper bounds for damage rolls per level. If set, each hit deals damage in the range
+    /// `damage..=max_damage`.
+    #[reflect(default)]
+    pub max_damage_by_level: Vec<usize>,
+    pub cooldown_by_level_ms: Vec<u64>,
+    /// Number of projectiles fired at each aim per level. Defaults to 1.
+    #[reflect(default)]
+    pub projectiles_by_level: Vec<usize>,
+    /// Angle in degrees that projectiles fired at the same aim are spread across. Defaults to 0.
+    #[reflect(default)]
+    pub spread_by_level_deg: Vec<f32>,
+    /// Offset relative to player from where projectiles should be launched, rotated toward the aim direction.
+    pub launch_offset: Vec2,
+}
+
+impl ActiveAbilityConfig
+{
+    fn get_damage(&self, level: usize) -> usize
+    {
+        get_for_level(&self.damage_by_level, level)
+    }
+
+    fn get_max_damage(&self, level: usize) -> Option<usize>
+    {
+        if self.max_damage_by_level.is_empty() {
+            return None;
+        }
+        Some(get_for_level(&self.max_damage_by_level, level))
+    }
+
+    fn get_cooldown(&self, level: usize, cdr: &CooldownReduction) -> Duration
+    {
+        let cooldown = get_for_level(&self.cooldown_by_level_ms, level);
+
+        // Apply cdr.
+        let cooldown = cdr.calculate_cooldown(cooldown);
+
+        Duration::from_millis(cooldown)
+    }
+
+    fn get_projectile_count(&self, level: usize) -> usize
+    {
+        get_for_level(&self.projectiles_by_level, level).max(1)
+    }
+
+    fn get_spread(&self, level: usize) -> f32
+    {
+        get_for_level(&self.spread_by_level_deg, level)
+    }
+
+    fn get_projectile(&self, level: usize) -> ProjectileConfig
+    {
+        let mut projectile = self.projectile.clone();
+        projectile.projectile_type = projectile
+            .projectile_type
+            .with_damage(self.get_damage(level));
+        projectile.max_damage = self.get_max_damage(level);
+        projectile
+    }
+
+    fn throw_projectile(
+        &self,
+        c: &mut Commands,
+        clock: &GameClock,
+        animations: &SpriteAnimations,
+        projectile: &ProjectileConfig,
+        source: Entity,
+        origin: Vec2,
+        dir: Dir2,
+        distance: f32,
+        area_size: &AreaSize,
+    )
+    {
+        let Some(effect_animation) = projectile.effect_animation.clone() else {
+            tracing::error!("failed throwing projectile for ability {:?}; effect_animation field is required but \
+                not set", self.name);
+            return;
+        };
+        let area = match projectile.projectile_type {
+            ProjectileType::Pulse { area, .. } | ProjectileType::Explosion { area, .. } => area,
+            _ => projectile.size,
+        };
+
+        // Spawn landing target.
+        let landing_target = c
+            .spawn((
+                SpatialBundle::from_transform(Transform::from_translation((origin + dir * distance).extend(0.))),
+                StateScoped(GameState::Play),
+                AttractionSource::LowPriority,
+                ThrowTarget,
+                AabbSize(Vec2::splat(1.)),
+            ))
+            .id();
+
+        // Spawn projectile, attracted to the landing target.
+        // - The projectile's velocity is used for attraction instead of straight-line travel.
+        // - When the projectile hits the landing target, despawn it and the projectile and spawn the projectile's
+        //   effect.
+        let thrown = ProjectileConfig { velocity_tps: 0., ..projectile.clone() };
+        let Some(entity) = thrown.create_projectile::<ThrowTarget>(
+            c,
+            clock,
+            animations,
+            source,
+            origin + dir.rotation_from_x() * self.launch_offset,
+            PlayerDirection::Right.into(),
+            area_size,
+            None,
+        ) else {
+            c.entity(landing_target).despawn_recursive();
+            return;
+        };
+        c.entity(entity).insert((
+            EffectZone::<ThrowTarget>::new(
+                EffectZoneConfig::Target { target: landing_target, cooldown_ms: 1_000_000 },
+                apply_thrown_projectile_landing,
+            ),
+            ThrownProjectile {
+                source,
+                damage: projectile.projectile_type.damage(),
+                max_damage: projectile.max_damage,
+                area: area_size.calculate_area(area),
+                effect_animation,
+                effect_sprite_layer: projectile
+                    .effect_sprite_layer
+                    .unwrap_or(SpriteLayer::Projectiles),
+                status_effects: projectile.status_effects.clone(),
+            },
+            Attraction::new(landing_target, projectile.velocity_tps, 0., Vec2::default(), 0., false),
+            AbilitySource(self.name.clone()),
+        ));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Active abilities, sorted by name.
+///
+/// Each `ActiveAbilityDatabase` command is merged into the existing database, replacing abilities with the same
+/// name. This way abilities can be defined in separate files.
+#[derive(Resource, Deref, Default, Reflect, Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ActiveAbilityDatabase(Vec<ActiveAbilityConfig>);
+
+impl ActiveAbilityDatabase
+{
+    fn insert(&mut self, config: ActiveAbilityConfig)
+    {
+        // Keep sorted so abilities always fire in the same order (this matters for determinism).
+        match self.0.binary_search_by(|c| c.name.cmp(&config.name)) {
+            Ok(idx) => self.0[idx] = config,
+            Err(idx) => self.0.insert(idx, config),
+        }
+    }
+}
+
+impl Command for ActiveAbilityDatabase
+{
+    fn apply(self, w: &mut World)
+    {
+        let mut bank = w.resource_mut::<PowerupBank>();
+        for config in self.iter() {
+            bank.register(PowerupInfo {
+                ability_type: AbilityType::Active,
+                name: config.name.clone(),
+                description: config.description.clone(),
+                icon: config.icon.clone(),
+            });
+        }
+
+        let mut database = w.resource_mut::<ActiveAbilityDatabase>();
+        for config in self.0 {
+            database.insert(config);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct ActiveAbilityPlugin;
+
+impl Plugin for ActiveAbilityPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_command::<ActiveAbilityDatabase>()
+            .init_resource::<ActiveAbilityDatabase>()
+            .add_systems(PreUpdate, add_active_ability_timers.run_if(in_state(PlayState::Day)))
+            .add_systems(Update, update_active_abilities.in_set(AbilitiesUpdateSet))
+            .add_effect_target::<ThrowTarget>();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,11 +1,5 @@
-mod beer_can;
-mod car_battery;
-mod large_tire;
-mod nail_gun;
+mod active_ability;
 mod plugin;
 
-pub use beer_can::*;
-pub use car_battery::*;
-pub use large_tire::*;
-pub use nail_gun::*;
+pub use active_ability::*;
 pub use plugin::*;
@@ -10,10 +10,7 @@ impl Plugin for AbilitiesPlugin
 {
     fn build(&self, app: &mut App)
     {
-        app.add_plugins(BeerCanPlugin)
-            .add_plugins(NailGunPlugin)
-            .add_plugins(CarBatteryPlugin)
-            .add_plugins(LargeTirePlugin);
+        app.add_plugins(ActiveAbilityPlugin);
     }
 }
 
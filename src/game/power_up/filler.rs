@@ -9,7 +9,7 @@ use crate::*;
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Filler power-ups when the player has maxed everything out.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FillerType
 {
     /// Recover % missing health.
@@ -27,8 +27,13 @@ impl FillerType
         data: Res<FillerDatabase>,
         mut karma: ReactResMut<Karma>,
         mut player: Query<&mut Health, With<Player>>,
+        recorder: Option<ResMut<DayRecorder>>,
     )
     {
+        if let Some(mut recorder) = recorder {
+            recorder.record_powerup(PowerupOption::Filler(filler_type));
+        }
+
         match filler_type {
             Self::Health => {
                 let Ok(mut hp) = player.get_single_mut() else { return };
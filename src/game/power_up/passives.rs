@@ -21,6 +21,8 @@ fn refresh_player_passives(
             &mut CollectionRange,
             &mut AreaSize,
             &mut DamageAmp,
+            &mut CritChance,
+            &mut CritDamage,
             &mut ExpAmp,
         ),
         With<Player>,
@@ -38,6 +40,8 @@ fn refresh_player_passives(
         mut _collection,
         mut areasize,
         mut damageamp,
+        mut critchance,
+        mut critdamage,
         mut expamp,
     )) = stats.get_single_mut()
     else {
@@ -53,6 +57,8 @@ fn refresh_player_passives(
     //collection.set_bonus(passives.get(Passive::CollectionRange, &player));
     areasize.set_bonus(passives.get(Passive::AreaSize, &player));
     damageamp.set_bonus(passives.get(Passive::DamageAmp, &player));
+    critchance.set_bonus(passives.get(Passive::CritChance, &player));
+    critdamage.set_bonus(passives.get(Passive::CritDamage, &player));
     expamp.set_bonus(passives.get(Passive::ExpAmp, &player));
 }
 
@@ -79,6 +85,10 @@ pub enum Passive
     AreaSize,
     /// Amplifies damage effects. Calculated as `damage*(1 + (damage_amp / 100))`.
     DamageAmp,
+    /// Increases the percent chance for damage to be a critical hit.
+    CritChance,
+    /// Amplifies critical hits. Calculated as `damage*(1 + (crit_damage / 100))`, applied after damage amp.
+    CritDamage,
     /// Amplifies how much experience is received. Calculated as `exp*(1 + (exp_amp / 100))`.
     ExpAmp,
 }
@@ -96,6 +106,8 @@ impl Passive
             //Self::CollectionRange => "Pickup Range",
             Self::AreaSize => "Area Size",
             Self::DamageAmp => "Damage Amp",
+            Self::CritChance => "Crit Chance",
+            Self::CritDamage => "Crit Damage",
             Self::ExpAmp => "Exp Amp",
         }
     }
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use bevy_cobweb::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::*;
 
@@ -24,7 +25,7 @@ fn refresh_player_powerups(
 
 //-------------------------------------------------------------------------------------------------------------------
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PowerupLevel
 {
     pub name: String,
@@ -33,7 +34,7 @@ pub struct PowerupLevel
 
 //-------------------------------------------------------------------------------------------------------------------
 
-#[derive(ReactResource, Debug, Default)]
+#[derive(ReactResource, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PlayerPowerups
 {
     /// Stored in a vec so they can be display in the order they were added.
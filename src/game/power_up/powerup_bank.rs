@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use bevy::prelude::*;
 
@@ -30,13 +30,33 @@ pub struct PowerupInfo
 //-------------------------------------------------------------------------------------------------------------------
 
 #[derive(Resource, Deref, Debug, Default)]
-pub struct PowerupBank(HashMap<String, PowerupInfo>);
+pub struct PowerupBank
+{
+    #[deref]
+    powerups: HashMap<String, PowerupInfo>,
+    /// Power-ups that can't be offered to the player as new power-ups.
+    locked: HashSet<String>,
+}
 
 impl PowerupBank
 {
     pub fn register(&mut self, info: PowerupInfo)
     {
-        self.0.insert(info.name.clone(), info);
+        self.powerups.insert(info.name.clone(), info);
+    }
+
+    pub fn set_locked(&mut self, name: impl Into<String>, locked: bool)
+    {
+        let name = name.into();
+        match locked {
+            true => self.locked.insert(name),
+            false => self.locked.remove(&name),
+        };
+    }
+
+    pub fn is_locked(&self, name: &str) -> bool
+    {
+        self.locked.contains(name)
     }
 }
 
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use bevy_cobweb::prelude::*;
 use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
 
 use crate::*;
 
@@ -28,6 +29,7 @@ fn handle_buffered_powerups(mut c: Commands, mut buffer: ResMut<BufferedPowerUps
 pub fn get_powerup_options(
     constants: &GameConstants,
     rng: &mut GameRng,
+    slots: &PowerupSlots,
     source: PowerupSource,
     player_powerups: &PlayerPowerups,
     powerup_bank: &PowerupBank,
@@ -44,12 +46,8 @@ pub fn get_powerup_options(
             AbilityType::Active => filled_active_slots += 1,
         });
 
-    let open_passive_slots = constants
-        .num_passive_slots
-        .saturating_sub(filled_passive_slots);
-    let open_active_slots = constants
-        .num_active_slots
-        .saturating_sub(filled_active_slots);
+    let open_passive_slots = slots.passive.saturating_sub(filled_passive_slots);
+    let open_active_slots = slots.active.saturating_sub(filled_active_slots);
 
     // Get candidate powerups.
     let mut candidates: Vec<PowerupOption> = powerup_bank
@@ -57,6 +55,9 @@ pub fn get_powerup_options(
         .filter_map(|(_, i)| {
             let level = player_powerups.get(&i.name);
             if level == 0 {
+                if powerup_bank.is_locked(&i.name) {
+                    return None;
+                }
                 if open_passive_slots == 0 && i.ability_type == AbilityType::Passive {
                     return None;
                 }
@@ -79,6 +80,8 @@ pub fn get_powerup_options(
     }
 
     // Randomize.
+    // - Sort first since `PowerupBank` iteration order is not deterministic, but replays need to be.
+    candidates.sort_unstable_by(|a, b| a.name().cmp(b.name()));
     candidates.shuffle(rng.rng());
 
     // Trim excess.
@@ -93,7 +96,7 @@ pub fn get_powerup_options(
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Types of power-up sources.
-#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum PowerupSource
 {
     #[default]
@@ -102,6 +105,50 @@ pub enum PowerupSource
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Number of power-up slots available to the player during the current day.
+///
+/// Set when a day starts from `GameConstants` and karma upgrades.
+#[derive(Resource, Default, Debug, Copy, Clone)]
+pub struct PowerupSlots
+{
+    pub passive: usize,
+    pub active: usize,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Number of times the player can reroll power-up options during the current day.
+#[derive(Resource, Default, Debug)]
+pub struct PowerupRerolls
+{
+    remaining: usize,
+}
+
+impl PowerupRerolls
+{
+    pub fn new(remaining: usize) -> Self
+    {
+        Self { remaining }
+    }
+
+    pub fn remaining(&self) -> usize
+    {
+        self.remaining
+    }
+
+    /// Tries to use a reroll.
+    pub fn spend(&mut self) -> bool
+    {
+        if self.remaining == 0 {
+            return false;
+        }
+        self.remaining -= 1;
+        true
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Coordinates collecting power-ups and handling them, in case multiple are collected at once.
 #[derive(Resource, Default)]
 pub struct BufferedPowerUps
@@ -131,6 +178,12 @@ impl BufferedPowerUps
         true
     }
 
+    /// Power-ups waiting to be handled, including the one currently being handled.
+    pub fn pending(&self) -> &[PowerupSource]
+    {
+        &self.buffer
+    }
+
     pub fn current_powerup(&self) -> Option<PowerupSource>
     {
         self.buffer.get(0).cloned()
@@ -151,7 +204,16 @@ impl BufferedPowerUps
 
 //-------------------------------------------------------------------------------------------------------------------
 
-#[derive(Debug, Clone)]
+/// Resource that causes power-ups to be resolved by selecting the first option instead of displaying the
+/// power-up UI.
+///
+/// Used when there is no player to make a choice (e.g. headless simulations).
+#[derive(Resource, Default, Debug)]
+pub struct PowerupAutoSelect;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PowerupType
 {
     New(String),
@@ -166,8 +228,13 @@ impl PowerupType
         constants: ReactRes<GameConstants>,
         powerup_bank: Res<PowerupBank>,
         mut player_powerups: ReactResMut<PlayerPowerups>,
+        recorder: Option<ResMut<DayRecorder>>,
     )
     {
+        if let Some(mut recorder) = recorder {
+            recorder.record_powerup(PowerupOption::Powerup(powerup_type.clone()));
+        }
+
         let player_powerups = player_powerups.get_mut(&mut c);
         match powerup_type {
             Self::New(name) => {
@@ -186,13 +253,34 @@ impl PowerupType
 
 //-------------------------------------------------------------------------------------------------------------------
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PowerupOption
 {
     Powerup(PowerupType),
     Filler(FillerType),
 }
 
+impl PowerupOption
+{
+    /// Gets the name of the option's power-up, or an empty string for filler options.
+    pub fn name(&self) -> &str
+    {
+        match self {
+            Self::Powerup(PowerupType::New(name) | PowerupType::Upgrade(name)) => name.as_str(),
+            Self::Filler(..) => "",
+        }
+    }
+
+    /// Applies the selected option to the world.
+    pub fn apply(self, c: &mut Commands)
+    {
+        match self {
+            Self::Powerup(powerup_type) => c.syscall(powerup_type, PowerupType::apply),
+            Self::Filler(filler_type) => c.syscall(filler_type, FillerType::apply),
+        }
+    }
+}
+
 //-------------------------------------------------------------------------------------------------------------------
 
 pub struct PowerupOptionsPlugin;
@@ -202,6 +290,8 @@ impl Plugin for PowerupOptionsPlugin
     fn build(&self, app: &mut App)
     {
         app.init_resource::<BufferedPowerUps>()
+            .init_resource::<PowerupSlots>()
+            .init_resource::<PowerupRerolls>()
             .add_systems(Update, handle_buffered_powerups.in_set(PowerUpActivateSet));
     }
 }
@@ -32,14 +32,30 @@ fn apply_projectile_effect_impl<T: Component>(
     In((projectile, target)): In<(Entity, Entity)>,
     mut c: Commands,
     mut events: EventWriter<DamageEvent>,
-    projectiles: Query<(&Transform, &Projectile)>,
+    mut status_events: EventWriter<StatusEffectEvent>,
+    projectiles: Query<(&Transform, &Projectile, Option<&AbilitySource>)>,
 )
 {
-    let Ok((transform, projectile)) = projectiles.get(projectile) else { return };
+    let Ok((transform, projectile, ability)) = projectiles.get(projectile) else { return };
 
     match projectile.projectile_type {
         ProjectileType::SingleUse { damage } | ProjectileType::Continuous { damage, .. } => {
-            events.send(DamageEvent { source: projectile.source, target, damage });
+            events.send(DamageEvent {
+                source: projectile.source,
+                target,
+                damage,
+                max_damage: projectile.max_damage,
+                ability: ability.map(|a| a.0.clone()),
+            });
+            let origin = transform.translation.truncate() - *projectile.direction;
+            for effect in projectile.status_effects.iter() {
+                status_events.send(StatusEffectEvent {
+                    source: projectile.source,
+                    target,
+                    effect: *effect,
+                    origin,
+                });
+            }
             if projectile.effect_animation.is_some() {
                 let mut ec = c.spawn_empty();
                 add_effect_animation(&mut ec, projectile, transform);
@@ -52,9 +68,16 @@ fn apply_projectile_effect_impl<T: Component>(
                     EffectZoneConfig::ApplyAndRegen { cooldown_ms: 1_000_000 },
                     apply_collider_effect,
                 ),
-                Collider { damage },
+                Collider {
+                    damage,
+                    max_damage: projectile.max_damage,
+                    status_effects: projectile.status_effects.clone(),
+                },
                 AabbSize(area),
             ));
+            if let Some(ability) = ability {
+                ec.insert(ability.clone());
+            }
 
             add_effect_animation(&mut ec, projectile, transform);
         }
@@ -65,9 +88,16 @@ fn apply_projectile_effect_impl<T: Component>(
                     EffectZoneConfig::ApplyAndRegen { cooldown_ms: 1_000_000 },
                     apply_collider_effect,
                 ),
-                Collider { damage },
+                Collider {
+                    damage,
+                    max_damage: projectile.max_damage,
+                    status_effects: projectile.status_effects.clone(),
+                },
                 AabbSize(area),
             ));
+            if let Some(ability) = ability {
+                ec.insert(ability.clone());
+            }
 
             add_effect_animation(&mut ec, projectile, transform);
         }
@@ -83,7 +113,7 @@ fn apply_projectile_effect<T: Component>(projectile: Entity, target: Entity, c:
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn update_projectile_transforms(
+pub(crate) fn update_projectile_transforms(
     mut c: Commands,
     clock: Res<GameClock>,
     constants: ReactRes<GameConstants>,
@@ -148,6 +178,29 @@ pub enum ProjectileType
 
 impl ProjectileType
 {
+    pub fn damage(&self) -> usize
+    {
+        match *self {
+            Self::SingleUse { damage }
+            | Self::Continuous { damage, .. }
+            | Self::Pulse { damage, .. }
+            | Self::Explosion { damage, .. } => damage,
+        }
+    }
+
+    pub fn with_damage(mut self, new_damage: usize) -> Self
+    {
+        match &mut self {
+            Self::SingleUse { damage }
+            | Self::Continuous { damage, .. }
+            | Self::Pulse { damage, .. }
+            | Self::Explosion { damage, .. } => {
+                *damage = new_damage;
+            }
+        }
+        self
+    }
+
     pub fn with_area_size(mut self, area_size: &AreaSize) -> Self
     {
         match &mut self {
@@ -180,6 +233,8 @@ pub struct Projectile
     velocity_tps: f32,
     direction: Dir2,
     despawn_time: Option<Duration>,
+    max_damage: Option<usize>,
+    status_effects: Vec<StatusEffect>,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -213,23 +268,28 @@ pub struct ProjectileConfig
     /// Defaults to `SpriteLayer::Projectiles`.
     #[reflect(default)]
     pub effect_sprite_layer: Option<SpriteLayer>,
+    /// If set, the projectile's damage is rolled in the range `damage..=max_damage` each time it hits.
+    #[reflect(default)]
+    pub max_damage: Option<usize>,
+    /// Status effects applied to targets hit by the projectile (or by its pulse/explosion).
+    #[reflect(default)]
+    pub status_effects: Vec<StatusEffect>,
 }
 
 impl ProjectileConfig
 {
-    pub fn create_projectile<T: Component>(
-        &self,
-        c: &mut Commands,
-        clock: &GameClock,
-        animations: &SpriteAnimations,
-        source: Entity,
-        spawn_location: Vec2,
-        direction: Dir2,
-        area_size: &AreaSize,
-        custom_applier: Option<fn(Entity, Entity, &mut Commands)>,
-    ) -> Option<Entity>
+    /// Scales the projectile's damage and max damage.
+    pub fn with_damage_multiplier(mut self, multiplier: f32) -> Self
+    {
+        self.projectile_type = self
+            .projectile_type
+            .with_damage(scale_stat(self.projectile_type.damage(), multiplier));
+        self.max_damage = self.max_damage.map(|d| scale_stat(d, multiplier));
+        self
+    }
+
+    fn effect_zone<T: Component>(&self, applier: fn(Entity, Entity, &mut Commands)) -> Option<EffectZone<T>>
     {
-        let applier = custom_applier.unwrap_or(apply_projectile_effect::<T>);
         let effect_zone = match self.projectile_type {
             ProjectileType::SingleUse { .. } => EffectZone::<T>::new(EffectZoneConfig::SelfDestruct, applier),
             ProjectileType::Continuous { cooldown_ms, .. } => {
@@ -255,6 +315,33 @@ impl ProjectileConfig
             }
         };
 
+        Some(effect_zone)
+    }
+
+    /// Adds an effect zone for another kind of target to a projectile spawned with `create_projectile`.
+    ///
+    /// The projectile's effects are applied to `T` targets the same way as to its original targets.
+    pub fn add_effect_target<T: Component>(&self, c: &mut Commands, projectile: Entity)
+    {
+        let Some(effect_zone) = self.effect_zone::<T>(apply_projectile_effect::<T>) else { return };
+        c.entity(projectile).try_insert(effect_zone);
+    }
+
+    pub fn create_projectile<T: Component>(
+        &self,
+        c: &mut Commands,
+        clock: &GameClock,
+        animations: &SpriteAnimations,
+        source: Entity,
+        spawn_location: Vec2,
+        direction: Dir2,
+        area_size: &AreaSize,
+        custom_applier: Option<fn(Entity, Entity, &mut Commands)>,
+    ) -> Option<Entity>
+    {
+        let applier = custom_applier.unwrap_or(apply_projectile_effect::<T>);
+        let effect_zone = self.effect_zone::<T>(applier)?;
+
         let effect_animation = self
             .effect_animation
             .as_ref()
@@ -280,6 +367,8 @@ impl ProjectileConfig
                     despawn_time: self
                         .max_lifetime_ms
                         .map(|l| clock.elapsed + Duration::from_millis(l)),
+                    max_damage: self.max_damage,
+                    status_effects: self.status_effects.clone(),
                 },
                 StateScoped(GameState::Play),
                 AabbSize(size),
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Places the current map theme's props.
+pub(crate) fn spawn_map_props(
+    mut c: Commands,
+    mut rng: ResMut<GameRng>,
+    day: ReactRes<Day>,
+    constants: ReactRes<GameConstants>,
+    images: Res<ImageMap>,
+    themes: Res<MapThemes>,
+    database: Res<PropDatabase>,
+)
+{
+    let Some(theme) = themes.get(day.get()) else { return };
+    let rng = rng.rng();
+
+    for (count, name) in theme.props.iter() {
+        let Some(data) = database.get(name) else {
+            tracing::error!("failed accessing prop data for {:?}, skipping prop", name);
+            continue;
+        };
+
+        for _ in 0..*count {
+            let Some(location) = random_map_location(rng, &constants, data.hitbox(), theme.clear_radius) else {
+                continue;
+            };
+            data.spawn(&mut c, name, rng, &images, location);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component for destructible props.
+///
+/// Props are damaged by player abilities, and drop their `CollectableDrop` when destroyed.
+#[derive(Component, Debug)]
+pub struct Prop
+{
+    /// Name of the prop in the `PropDatabase`.
+    pub name: String,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropData
+{
+    /// Image from the `ImageMap`.
+    pub image: String,
+    /// Size of the displayed image.
+    pub size: Vec2,
+    /// Defaults to `size`.
+    #[reflect(default)]
+    pub hitbox: Option<Vec2>,
+    #[reflect(default)]
+    pub tint: Color,
+    pub health: usize,
+    #[reflect(default)]
+    pub armor: usize,
+    /// [ (probability, drop) ]
+    ///
+    /// One drop is selected when the prop spawns, and dropped when it is destroyed.
+    #[reflect(default)]
+    pub drops: Vec<(f32, CollectableDrop)>,
+}
+
+impl PropData
+{
+    pub fn hitbox(&self) -> Vec2
+    {
+        self.hitbox.unwrap_or(self.size)
+    }
+
+    pub fn spawn(
+        &self,
+        c: &mut Commands,
+        name: &str,
+        rng: &mut ChaCha8Rng,
+        images: &ImageMap,
+        location: Vec2,
+    ) -> Entity
+    {
+        let mut ec = c.spawn((
+            Prop { name: name.into() },
+            SpriteBundle {
+                texture: images.get(&self.image),
+                sprite: Sprite { color: self.tint, custom_size: Some(self.size), ..default() },
+                transform: Transform::from_translation(location.extend(0.)),
+                ..default()
+            },
+            AabbSize(self.hitbox()),
+            Health::new(self.health),
+            Armor::new(self.armor),
+            DespawnOnDeath,
+            SpriteLayer::Objects,
+            StateScoped(GameState::Play),
+        ));
+
+        if let Some(drop) = self.select_collectable_drop(rng) {
+            ec.insert(drop);
+        }
+
+        ec.id()
+    }
+
+    fn select_collectable_drop(&self, rng: &mut ChaCha8Rng) -> Option<CollectableDrop>
+    {
+        let selection = rng.gen_range((0.)..(1.));
+        let mut accumulated = 0.;
+        for (probability, drop) in self.drops.iter() {
+            accumulated += probability;
+            if accumulated < selection {
+                continue;
+            }
+            return Some(drop.clone());
+        }
+        None
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// [prop name, prop data]
+#[derive(Resource, Deref, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropDatabase(HashMap<String, PropData>);
+
+impl Command for PropDatabase
+{
+    fn apply(self, w: &mut World)
+    {
+        w.insert_resource(self);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct PropsPlugin;
+
+impl Plugin for PropsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_command::<PropDatabase>()
+            .init_resource::<PropDatabase>()
+            // Props use `GameRng`, so they must be placed after the day's rng state is recorded or restored.
+            // - Resumed days restore their surviving props from the day snapshot instead.
+            .add_systems(
+                OnEnter(PlayState::Day),
+                spawn_map_props
+                    .after(DayRngSetupSet)
+                    .after(spawn_map_hazards)
+                    .run_if(not(resource_exists::<PendingDaySnapshot>)),
+            );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
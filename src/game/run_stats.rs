@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Name used for player damage that wasn't dealt by an ability (e.g. damage-over-time effects).
+const OTHER_DAMAGE: &str = "Other";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn reset_run_stats(mut stats: ResMut<RunStats>)
+{
+    *stats = RunStats::default();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn track_damage(
+    mut stats: ResMut<RunStats>,
+    mut damage: EventReader<DamageApplied>,
+    player: Query<Entity, With<Player>>,
+    mobs: Query<(), With<Mob>>,
+)
+{
+    let Ok(player_entity) = player.get_single() else { return };
+
+    for DamageApplied { source, target, amount, ability, .. } in damage.read() {
+        if *target == player_entity {
+            stats.damage_taken += amount;
+            continue;
+        }
+
+        // Only count damage the player dealt to mobs.
+        if !mobs.contains(*target) || (ability.is_none() && *source != player_entity) {
+            continue;
+        }
+        let ability = ability.as_deref().unwrap_or(OTHER_DAMAGE);
+        *stats.ability_damage.entry(ability.into()).or_default() += amount;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn track_kills(
+    mut stats: ResMut<RunStats>,
+    mut deaths: EventReader<EntityDeath>,
+    mobs: Query<&MobSpawnInfo>,
+)
+{
+    for EntityDeath(entity) in deaths.read() {
+        let Ok(info) = mobs.get(*entity) else { continue };
+        *stats.kills.entry(info.name.clone()).or_default() += 1;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn track_collectables(mut stats: ResMut<RunStats>, mut collected: EventReader<CollectableApplied>)
+{
+    for CollectableApplied(collectable) in collected.read() {
+        match collectable {
+            Collectable::Exp(exp) => stats.exp_collected += exp,
+            Collectable::Karma(karma) => stats.karma_collected += karma,
+            Collectable::HealthPack => (),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn track_progress(mut stats: ResMut<RunStats>, clock: Res<GameClock>, player: Query<&Level, With<Player>>)
+{
+    stats.time_survived = clock.elapsed;
+    if let Ok(level) = player.get_single() {
+        stats.level = level.level();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn record_day_failed(
+    mut c: Commands,
+    day: ReactRes<Day>,
+    stats: Res<RunStats>,
+    mut history: ReactResMut<RunHistory>,
+)
+{
+    history
+        .get_mut(&mut c)
+        .record(DayStatsRecord { day: day.get(), survived: false, stats: stats.clone() });
+}
+
+fn record_day_survived(
+    mut c: Commands,
+    day: ReactRes<Day>,
+    stats: Res<RunStats>,
+    mut history: ReactResMut<RunHistory>,
+)
+{
+    history
+        .get_mut(&mut c)
+        .record(DayStatsRecord { day: day.get(), survived: true, stats: stats.clone() });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Statistics for the current day.
+///
+/// Reset when a day starts.
+#[derive(Resource, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunStats
+{
+    /// [ mob name : kills ]
+    pub kills: HashMap<String, usize>,
+    /// [ ability name : damage dealt to mobs ]
+    pub ability_damage: HashMap<String, usize>,
+    pub damage_taken: usize,
+    pub exp_collected: usize,
+    pub karma_collected: usize,
+    /// Level the player reached.
+    pub level: usize,
+    pub time_survived: Duration,
+}
+
+impl RunStats
+{
+    pub fn total_kills(&self) -> usize
+    {
+        self.kills.values().sum()
+    }
+
+    pub fn total_damage(&self) -> usize
+    {
+        self.ability_damage.values().sum()
+    }
+
+    /// Gets kills sorted from most to least.
+    pub fn sorted_kills(&self) -> Vec<(&str, usize)>
+    {
+        Self::sorted(&self.kills)
+    }
+
+    /// Gets damage dealt by each ability, sorted from most to least.
+    pub fn sorted_ability_damage(&self) -> Vec<(&str, usize)>
+    {
+        Self::sorted(&self.ability_damage)
+    }
+
+    fn sorted(map: &HashMap<String, usize>) -> Vec<(&str, usize)>
+    {
+        let mut sorted: Vec<_> = map.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+        sorted.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        sorted
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Stats for a day that ended.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayStatsRecord
+{
+    pub day: usize,
+    /// Whether the player survived the day.
+    pub survived: bool,
+    pub stats: RunStats,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Stats of every day played, in the order they were played. Persisted in the save.
+#[derive(ReactResource, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunHistory
+{
+    days: Vec<DayStatsRecord>,
+}
+
+impl RunHistory
+{
+    pub fn record(&mut self, record: DayStatsRecord)
+    {
+        self.days.push(record);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &DayStatsRecord>
+    {
+        self.days.iter()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct RunStatsPlugin;
+
+impl Plugin for RunStatsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<RunStats>()
+            .init_react_resource::<RunHistory>()
+            .add_systems(OnEnter(PlayState::Day), reset_run_stats)
+            .add_systems(
+                PreUpdate,
+                track_progress
+                    .after(GameClockUpdateSet)
+                    .run_if(in_state(PlayState::Day)),
+            )
+            // Deaths are tracked before they are handled so the final blow of a day is counted.
+            .add_systems(
+                Update,
+                (track_damage, track_kills, track_collectables)
+                    .after(handle_damage_events)
+                    .in_set(DamageSet::DetectDamage),
+            )
+            .react(|rc| rc.on_persistent(broadcast::<PlayerDied>(), record_day_failed))
+            .react(|rc| rc.on_persistent(broadcast::<PlayerSurvived>(), record_day_survived));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,227 @@
+use bevy::math::bounding::{Aabb2d, BoundingVolume};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_cobweb::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn update_spatial_grid(
+    mut grid: ResMut<SpatialGrid>,
+    constants: ReactRes<GameConstants>,
+    entities: Query<(Entity, &Transform, &AabbSize)>,
+)
+{
+    grid.clear(constants.spatial_grid_cell_size, constants.spatial_grid_margin);
+    for (entity, transform, aabb) in entities.iter() {
+        grid.insert(entity, aabb.get_2d(transform));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn clear_spatial_grid(mut grid: ResMut<SpatialGrid>)
+{
+    grid.cells.clear();
+    grid.occupied = None;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Copy, Clone)]
+struct GridEntry
+{
+    entity: Entity,
+    /// Lowest cell overlapped by the entity. Used to deduplicate entities that overlap multiple cells.
+    min_cell: IVec2,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Uniform grid of all entities with `AabbSize` and `Transform`, for fast proximity queries.
+///
+/// The grid is rebuilt in [`PrevLocationUpdateSet`], and again after [`AttractionUpdateSet`] once the player and
+/// mobs have moved for the tick. Entities that move or spawn between rebuilds may be out of date, so queries are
+/// padded by `GameConstants::spatial_grid_margin` and callers should always do a precise check against the current
+/// `Transform` of candidates.
+#[derive(Resource, Debug)]
+pub struct SpatialGrid
+{
+    cell_size: f32,
+    margin: f32,
+    /// Cells are not removed when cleared so their allocations can be reused.
+    cells: HashMap<IVec2, Vec<GridEntry>>,
+    /// Lowest and highest cells that contain entities. Queries never visit cells outside this range.
+    occupied: Option<(IVec2, IVec2)>,
+}
+
+impl SpatialGrid
+{
+    fn clear(&mut self, cell_size: f32, margin: f32)
+    {
+        self.cell_size = cell_size.max(1.);
+        self.margin = margin.max(0.);
+        for entries in self.cells.values_mut() {
+            entries.clear();
+        }
+        self.occupied = None;
+    }
+
+    fn insert(&mut self, entity: Entity, aabb: Aabb2d)
+    {
+        let (min_cell, max_cell) = self.cell_range(aabb);
+        self.occupied = Some(match self.occupied {
+            Some((min, max)) => (min.min(min_cell), max.max(max_cell)),
+            None => (min_cell, max_cell),
+        });
+        for x in min_cell.x..=max_cell.x {
+            for y in min_cell.y..=max_cell.y {
+                self.cells
+                    .entry(IVec2 { x, y })
+                    .or_default()
+                    .push(GridEntry { entity, min_cell });
+            }
+        }
+    }
+
+    fn cell(&self, point: Vec2) -> IVec2
+    {
+        (point / self.cell_size).floor().as_ivec2()
+    }
+
+    fn cell_range(&self, aabb: Aabb2d) -> (IVec2, IVec2)
+    {
+        (self.cell(aabb.min), self.cell(aabb.max))
+    }
+
+    /// Calls `callback` once for every entity whose cells overlap `aabb` (padded by the grid margin).
+    ///
+    /// Entities are visited in a deterministic order.
+    pub fn for_each_in_aabb(&self, aabb: Aabb2d, mut callback: impl FnMut(Entity))
+    {
+        let Some((min_occupied, max_occupied)) = self.occupied else { return };
+        let (min_cell, max_cell) = self.cell_range(aabb.grow(Vec2::splat(self.margin)));
+        let (min_cell, max_cell) = (min_cell.max(min_occupied), max_cell.min(max_occupied));
+        for x in min_cell.x..=max_cell.x {
+            for y in min_cell.y..=max_cell.y {
+                let cell = IVec2 { x, y };
+                let Some(entries) = self.cells.get(&cell) else { continue };
+                for entry in entries.iter() {
+                    // Only visit an entity in the first cell where it overlaps the query.
+                    if entry.min_cell.max(min_cell) != cell {
+                        continue;
+                    }
+                    callback(entry.entity);
+                }
+            }
+        }
+    }
+
+    /// Calls `callback` once for every entity whose cells overlap the bounding box of a circle (padded by the grid
+    /// margin).
+    pub fn for_each_in_radius(&self, center: Vec2, radius: f32, callback: impl FnMut(Entity))
+    {
+        self.for_each_in_aabb(Aabb2d::new(center, Vec2::splat(radius.max(0.))), callback);
+    }
+
+    /// Finds the nearest entity to `point` within `max_distance`.
+    ///
+    /// The `position` callback should return the current position of an entity, or `None` if the entity should
+    /// be ignored. Returns `(entity, position, distance)`.
+    pub fn nearest(
+        &self,
+        point: Vec2,
+        max_distance: f32,
+        mut position: impl FnMut(Entity) -> Option<Vec2>,
+    ) -> Option<(Entity, Vec2, f32)>
+    {
+        let Some((min_occupied, max_occupied)) = self.occupied else { return None };
+        let center_cell = self.cell(point);
+
+        // Rings past the farthest occupied cell are empty, so `max_distance` doesn't need to be bounded.
+        let occupied_ring = [
+            center_cell.x as i64 - min_occupied.x as i64,
+            max_occupied.x as i64 - center_cell.x as i64,
+            center_cell.y as i64 - min_occupied.y as i64,
+            max_occupied.y as i64 - center_cell.y as i64,
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or_default();
+        let range_ring = ((max_distance + self.margin) / self.cell_size).ceil() as f64 + 1.;
+        let max_ring = range_ring.min(occupied_ring as f64).max(0.) as i32;
+        let mut best: Option<(Entity, Vec2, f32)> = None;
+
+        for ring in 0..=max_ring {
+            let min_cell = (center_cell - IVec2::splat(ring)).max(min_occupied);
+            let max_cell = (center_cell + IVec2::splat(ring)).min(max_occupied);
+            for x in min_cell.x..=max_cell.x {
+                for y in min_cell.y..=max_cell.y {
+                    // Only visit cells on the edge of the ring.
+                    if (x - center_cell.x).abs() != ring && (y - center_cell.y).abs() != ring {
+                        continue;
+                    }
+                    let Some(entries) = self.cells.get(&IVec2 { x, y }) else { continue };
+                    for entry in entries.iter() {
+                        let Some(pos) = position(entry.entity) else { continue };
+                        let distance = pos.distance(point);
+                        if distance > max_distance {
+                            continue;
+                        }
+                        if best.map(|(_, _, d)| distance >= d).unwrap_or(false) {
+                            continue;
+                        }
+                        best = Some((entry.entity, pos, distance));
+                    }
+                }
+            }
+
+            // Entities in farther rings can't be closer than the best found so far.
+            if let Some((_, _, distance)) = best {
+                if distance + self.margin <= (ring as f32) * self.cell_size {
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+}
+
+impl Default for SpatialGrid
+{
+    fn default() -> Self
+    {
+        Self {
+            cell_size: 64.,
+            margin: 0.,
+            cells: HashMap::default(),
+            occupied: None,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct SpatialGridPlugin;
+
+impl Plugin for SpatialGridPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_resource::<SpatialGrid>()
+            .add_systems(Update, update_spatial_grid.in_set(PrevLocationUpdateSet))
+            // Refresh the grid after movement so effects and ability targeting see where entities are now.
+            .add_systems(
+                Update,
+                update_spatial_grid
+                    .after(AttractionUpdateSet)
+                    .before(ProjectileUpdateSet)
+                    .run_if(in_state(PlayState::Day)),
+            )
+            .add_systems(OnExit(GameState::Play), clear_spatial_grid);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -3,7 +3,6 @@ use std::time::Duration;
 
 use bevy::ecs::world::Command;
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 use bevy_cobweb::prelude::*;
 use bevy_cobweb_ui::prelude::*;
 use rand::Rng;
@@ -13,18 +12,20 @@ use crate::*;
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn spawn_mobs(
-    mut event_count: Local<usize>,
+pub(crate) fn spawn_mobs(
     mut c: Commands,
     clock: Res<GameClock>,
     constants: ReactRes<GameConstants>,
     mob_data: Res<MobDatabase>,
+    elite_settings: Res<EliteSettings>,
+    hazard_data: Res<HazardDatabase>,
+    images: Res<ImageMap>,
+    multipliers: Res<MobStatMultipliers>,
     animations: Res<SpriteAnimations>,
     mut rng: ResMut<GameRng>,
     mut sequence: ResMut<SpawnSequence>,
     mut active_events: ResMut<ActiveEvents>,
-    camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
-    window: Query<&Window, With<PrimaryWindow>>,
+    viewport: CameraViewport,
     player: Query<(Entity, &Transform), With<Player>>,
     mobs: Query<&InSpawnEvent, With<Mob>>,
 )
@@ -37,8 +38,9 @@ fn spawn_mobs(
         }
         let last = sequence.sequence.pop().unwrap();
         //tracing::error!("adding event {:?} {:?}", last, clock.elapsed);
-        active_events.push(ActiveSpawnEvent::new(*event_count, last, clock.elapsed));
-        *event_count += 1;
+        let id = active_events.next_id;
+        active_events.next_id += 1;
+        active_events.push(ActiveSpawnEvent::new(id, last, clock.elapsed));
     }
 
     if active_events.len() == 0 {
@@ -55,9 +57,7 @@ fn spawn_mobs(
     }
 
     // Compute spawn radius from window radius and buffer factor.
-    let (camera, cam_global) = camera.single();
-    let window = window.single();
-    let (cam_lower_left, cam_upper_right) = get_camera_corners(&camera, &cam_global, &window);
+    let (cam_lower_left, cam_upper_right) = viewport.corners();
     let viewport_radius = Vec2 {
         x: (cam_upper_right.x - cam_lower_left.x) / 2.,
         y: (cam_upper_right.y - cam_lower_left.y) / 2.,
@@ -76,8 +76,34 @@ fn spawn_mobs(
         alive_count,
         total_wave_size,
         total_spawned,
+        boss_spawned,
     } in active_events.iter_mut()
     {
+        // Spawn the event's boss once.
+        if let Some(boss_name) = event.boss.as_ref().filter(|_| !*boss_spawned) {
+            *boss_spawned = true;
+            match mob_data.get(boss_name) {
+                Some(boss_data) => {
+                    let direction = rng.gen_range((0.)..TAU);
+                    let location =
+                        player_transform.translation.truncate() + Vec2::from_angle(direction) * spawn_radius;
+                    boss_data.spawn(
+                        &mut c,
+                        boss_name,
+                        rng,
+                        &constants,
+                        Transform::from_translation(location.extend(0.)),
+                        player_entity,
+                        &animations,
+                        *id,
+                        &multipliers,
+                        None,
+                    );
+                }
+                None => tracing::error!("failed accessing mob data for boss {:?}, skipping spawn", boss_name),
+            }
+        }
+
         // Check if on a spawn time.
         if clock.elapsed < *next_spawn_time {
             continue;
@@ -95,6 +121,9 @@ fn spawn_mobs(
             0.
         };
 
+        // Events can override the day's elite chance.
+        let elite_chance = event.elite_chance.unwrap_or(sequence.elite_chance);
+
         // Spawn each desired mob.
         for (count, mob_name) in event.mobs_per_wave.iter() {
             // Adjust size proportional to extra needed.
@@ -131,21 +160,50 @@ fn spawn_mobs(
                 let mut entity_transform = point_transform;
                 entity_transform.translation += adjustment.extend(0.);
 
+                // Roll for elite.
+                let elite = match elite_chance > 0. && rng.gen_range((0.)..(1.)) < elite_chance {
+                    true => elite_settings.roll(rng),
+                    false => None,
+                };
+
                 // SPAWN IT
                 mob_data.spawn(
                     &mut c,
+                    mob_name,
                     rng,
                     &constants,
                     entity_transform,
                     player_entity,
                     &animations,
                     *id,
+                    &multipliers,
+                    elite.as_ref(),
                 );
             }
 
             // Count it.
             *total_spawned += count;
         }
+
+        // Spawn hazards near the player.
+        for (count, hazard_name) in event.hazards_per_wave.iter() {
+            let Some(hazard) = hazard_data.get(hazard_name) else {
+                tracing::error!("failed accessing hazard data for {:?}, skipping spawn", hazard_name);
+                continue;
+            };
+
+            for _ in 0..*count {
+                hazard.spawn_near(
+                    &mut c,
+                    rng,
+                    &clock,
+                    &constants,
+                    &images,
+                    player_transform.translation.truncate(),
+                    viewport_radius,
+                );
+            }
+        }
     }
 
     // Clean up empty events.
@@ -154,7 +212,7 @@ fn spawn_mobs(
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn insert_spawn_sequence(mut c: Commands, day: ReactRes<Day>, schedule: Res<SpawnSchedule>)
+pub(crate) fn insert_spawn_sequence(mut c: Commands, day: ReactRes<Day>, schedule: Res<SpawnSchedule>)
 {
     let sch = &schedule.schedule;
     // If we run out of sequences, replay the last day.
@@ -170,8 +228,9 @@ fn insert_spawn_sequence(mut c: Commands, day: ReactRes<Day>, schedule: Res<Spaw
 
 //-------------------------------------------------------------------------------------------------------------------
 
-#[derive(Debug, Default)]
-struct ActiveSpawnEvent
+/// A spawn event that has started.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveSpawnEvent
 {
     id: usize,
     event: SpawnEvent,
@@ -180,6 +239,7 @@ struct ActiveSpawnEvent
     alive_count: usize,
     next_spawn_time: Duration,
     total_spawned: usize,
+    boss_spawned: bool,
 }
 
 impl ActiveSpawnEvent
@@ -200,14 +260,22 @@ impl ActiveSpawnEvent
             alive_count: 0,
             next_spawn_time: current_time,
             total_spawned: 0,
+            boss_spawned: false,
         }
     }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
-#[derive(Resource, Deref, DerefMut, Debug, Default)]
-struct ActiveEvents(Vec<ActiveSpawnEvent>);
+/// Spawn events that have started and not ended yet.
+#[derive(Resource, Deref, DerefMut, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveEvents
+{
+    #[deref]
+    events: Vec<ActiveSpawnEvent>,
+    /// Id for the next event that starts. Mobs reference their event by id with `InSpawnEvent`.
+    next_id: usize,
+}
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -226,6 +294,17 @@ pub struct SpawnEvent
     /// Waits until this many total have spawned before amplified future waves.
     pub min_alive: usize,
     pub mobs_per_wave: Vec<(usize, String)>,
+    /// Mob spawned once when the event starts. Usually a mob with `MobType::Boss`.
+    #[reflect(default)]
+    pub boss: Option<String>,
+    /// Chance (0-1) for each mob in a wave to spawn as an elite. Overrides `SpawnSequence::elite_chance`.
+    #[reflect(default)]
+    pub elite_chance: Option<f32>,
+    /// [ (count, hazard name) ]
+    ///
+    /// Hazards spawned each wave at random locations near the player. See [`HazardDatabase`].
+    #[reflect(default)]
+    pub hazards_per_wave: Vec<(usize, String)>,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -235,6 +314,9 @@ pub struct SpawnSequence
 {
     day: usize,
     sequence: Vec<SpawnEvent>,
+    /// Chance (0-1) for each mob spawned in a wave to spawn as an elite. See [`EliteSettings`].
+    #[reflect(default)]
+    elite_chance: f32,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
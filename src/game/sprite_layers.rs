@@ -19,6 +19,7 @@ pub enum SpriteLayer
     Projectiles,
     PlayerBillboardLv1,
     PlayerBillboardLv2,
+    DamageNumbers,
 }
 
 impl LayerIndex for SpriteLayer
@@ -35,6 +36,7 @@ impl LayerIndex for SpriteLayer
             Projectiles => 5.,
             PlayerBillboardLv1 => 6.,
             PlayerBillboardLv2 => 7.,
+            DamageNumbers => 8.,
         }
     }
 }
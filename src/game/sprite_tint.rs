@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resolves sprite colors from their tint layers. This is the only system that should write tints to
+/// `Sprite::color`.
+fn update_sprite_tints(
+    constants: ReactRes<GameConstants>,
+    mut entities: Query<(&mut SpriteTint, &mut Sprite, Option<&StatusEffects>, Option<&BaseTint>)>,
+)
+{
+    for (mut tint, mut sprite, maybe_effects, maybe_base_tint) in entities.iter_mut() {
+        let color = tint
+            .telegraph
+            .or_else(|| maybe_effects.and_then(|e| e.tint(&constants)))
+            .or_else(|| maybe_base_tint.map(|t| t.0))
+            .unwrap_or(Color::WHITE);
+
+        // Only write when the color changes so other sprite effects (e.g. invulnerability blinks) aren't reset.
+        if tint.applied == Some(color) {
+            continue;
+        }
+        tint.applied = Some(color);
+        sprite.color = color;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component that controls the color of an entity's sprite.
+///
+/// The sprite color is the first of these that is set: `telegraph`, the [`StatusEffects`] tint, [`BaseTint`],
+/// `Color::WHITE`.
+#[derive(Component, Debug, Default)]
+pub struct SpriteTint
+{
+    /// Tint shown while an attack is telegraphed (e.g. before a charge).
+    pub telegraph: Option<Color>,
+    applied: Option<Color>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct SpriteTintPlugin;
+
+impl Plugin for SpriteTintPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.add_systems(
+            Update,
+            update_sprite_tints
+                .after(MobUpdateSet)
+                .before(DamageUpdateSet)
+                .run_if(in_state(PlayState::Day)),
+        );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
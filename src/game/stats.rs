@@ -3,24 +3,33 @@
 use std::time::Duration;
 
 use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::*;
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn apply_health_regen(clock: Res<GameClock>, mut regen: Query<(&mut HealthRegen, &mut Health)>)
+fn apply_health_regen(
+    mut heals: EventWriter<HealApplied>,
+    clock: Res<GameClock>,
+    mut regen: Query<(Entity, &mut HealthRegen, &mut Health)>,
+)
 {
-    for (mut regen, mut health) in regen.iter_mut() {
+    for (entity, mut regen, mut health) in regen.iter_mut() {
         if !regen.try_next(clock.elapsed) {
             continue;
         }
-        health.add(regen.current());
+        let amount = health.add(regen.current());
+        if amount > 0 {
+            heals.send(HealApplied { target: entity, amount });
+        }
     }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
-#[derive(Debug, Component)]
+#[derive(Debug, Component, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Health
 {
     current: usize,
@@ -50,10 +59,13 @@ impl Health
         self.base_max + self.bonus
     }
 
-    pub fn add(&mut self, add: usize)
+    /// Returns the amount of health actually added.
+    pub fn add(&mut self, add: usize) -> usize
     {
+        let prev = self.current;
         self.current += add;
         self.current = self.current.min(self.max());
+        self.current.saturating_sub(prev)
     }
 
     pub fn remove(&mut self, sub: usize)
@@ -294,6 +306,82 @@ impl DamageAmp
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Percent chance (0-100) for damage to be a critical hit.
+#[derive(Debug, Component)]
+pub struct CritChance
+{
+    base: usize,
+    bonus: usize,
+}
+
+impl CritChance
+{
+    pub fn new(base: usize) -> Self
+    {
+        Self { base, bonus: 0 }
+    }
+
+    pub fn current(&self) -> usize
+    {
+        self.base + self.bonus
+    }
+
+    /// Rolls for a critical hit.
+    ///
+    /// The rng is only used if the crit chance is between 0 and 100.
+    pub fn roll(&self, rng: &mut impl Rng) -> bool
+    {
+        let chance = self.current();
+        if chance == 0 {
+            return false;
+        }
+        if chance >= 100 {
+            return true;
+        }
+        rng.gen_range(0..100) < chance
+    }
+
+    pub fn set_bonus(&mut self, bonus: usize)
+    {
+        self.bonus = bonus;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Amplifies critical hits. Calculated as `damage*(1 + (crit_damage / 100))`.
+#[derive(Debug, Component)]
+pub struct CritDamage
+{
+    base: usize,
+    bonus: usize,
+}
+
+impl CritDamage
+{
+    pub fn new(base: usize) -> Self
+    {
+        Self { base, bonus: 0 }
+    }
+
+    pub fn current(&self) -> usize
+    {
+        self.base + self.bonus
+    }
+
+    pub fn calculate_damage(&self, damage: f32) -> f32
+    {
+        damage + damage * (self.current() as f32) / 100.
+    }
+
+    pub fn set_bonus(&mut self, bonus: usize)
+    {
+        self.bonus = bonus;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Debug, Component)]
 pub struct ExpAmp
 {
@@ -326,7 +414,7 @@ impl ExpAmp
 
 //-------------------------------------------------------------------------------------------------------------------
 
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Level
 {
     level: usize,
@@ -383,6 +471,16 @@ impl Level
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Event emitted when an entity's `Health` is restored.
+#[derive(Event, Debug, Copy, Clone)]
+pub struct HealApplied
+{
+    pub target: Entity,
+    pub amount: usize,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(SystemSet, Hash, Eq, PartialEq, Debug, Copy, Clone)]
 pub struct StatsUpdateSet;
 
@@ -394,7 +492,8 @@ impl Plugin for StatsPlugin
 {
     fn build(&self, app: &mut App)
     {
-        app.add_systems(Update, apply_health_regen.in_set(StatsUpdateSet));
+        app.add_state_scoped_event::<HealApplied>(PlayState::Day)
+            .add_systems(Update, apply_health_regen.in_set(StatsUpdateSet));
     }
 }
 
@@ -0,0 +1,353 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Max number of poison stacks that can be on an entity at once. The oldest stack is removed when a new stack
+/// would exceed the limit.
+const MAX_POISON_STACKS: usize = 10;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn handle_status_effect_events(
+    mut events: EventReader<StatusEffectEvent>,
+    clock: Res<GameClock>,
+    mut targets: Query<(&mut StatusEffects, &Transform, &Health)>,
+)
+{
+    let time = clock.elapsed;
+
+    for StatusEffectEvent { source, target, effect, origin } in events.read() {
+        let Ok((mut effects, transform, hp)) = targets.get_mut(*target) else { continue };
+
+        // Check if entity is already dead.
+        if hp.current() == 0 {
+            continue;
+        }
+
+        let direction = transform.translation.truncate() - *origin;
+        effects.apply(*effect, *source, direction, time);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn update_status_effects(
+    mut damage_events: EventWriter<DamageEvent>,
+    clock: Res<GameClock>,
+    mut entities: Query<(Entity, &mut StatusEffects)>,
+)
+{
+    let time = clock.elapsed;
+
+    for (entity, mut effects) in entities.iter_mut() {
+        // Update timers and apply damage-over-time ticks.
+        effects.update(time, |source, damage| {
+            damage_events.send(DamageEvent {
+                source,
+                target: entity,
+                damage,
+                max_damage: None,
+                ability: None,
+            });
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Knockback is movement, so it's applied after `PrevLocationUpdateSet` like other movement. This way collision
+/// checks that sweep from the previous location include it.
+fn apply_knockback(clock: Res<GameClock>, mut entities: Query<(&StatusEffects, &mut Transform)>)
+{
+    let delta_secs = clock.delta.as_secs_f32();
+
+    for (effects, mut transform) in entities.iter_mut() {
+        let Some(knockback) = &effects.knockback else { continue };
+        transform.translation += (knockback.velocity * delta_secs).extend(0.);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A timed effect that can be applied to entities with [`StatusEffects`].
+///
+/// Stacking rules:
+/// - `Poison`: each application adds a separate stack, up to 10 stacks.
+/// - `Burn`: does not stack. Reapplying keeps the highest damage and refreshes the duration.
+/// - `Slow`: does not stack. The strongest slow applies, and reapplying an equal slow refreshes the duration.
+/// - `Stun`: does not stack. Reapplying extends the stun if the new stun ends later.
+/// - `Knockback`: replaces the current knockback.
+#[derive(Reflect, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StatusEffect
+{
+    /// Deals `damage` every `tick_ms` until the effect ends.
+    Poison
+    {
+        damage: usize, tick_ms: u64, duration_ms: u64
+    },
+    /// Deals `damage` every `tick_ms` until the effect ends.
+    Burn
+    {
+        damage: usize, tick_ms: u64, duration_ms: u64
+    },
+    /// Reduces movement speed by `percent` (0-100).
+    Slow
+    {
+        percent: f32, duration_ms: u64
+    },
+    /// Prevents movement and emitter attacks.
+    Stun
+    {
+        duration_ms: u64
+    },
+    /// Pushes the target `distance` transform units away from the effect's origin over `duration_ms`.
+    Knockback
+    {
+        distance: f32, duration_ms: u64
+    },
+}
+
+impl Default for StatusEffect
+{
+    fn default() -> Self
+    {
+        Self::Stun { duration_ms: 0 }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Copy, Clone)]
+struct DamageOverTime
+{
+    source: Entity,
+    damage: usize,
+    tick: Duration,
+    next_tick: Duration,
+    end: Duration,
+}
+
+impl DamageOverTime
+{
+    fn new(source: Entity, damage: usize, tick_ms: u64, duration_ms: u64, time: Duration) -> Self
+    {
+        let tick = Duration::from_millis(tick_ms.max(1));
+        Self {
+            source,
+            damage,
+            tick,
+            next_tick: time + tick,
+            end: time + Duration::from_millis(duration_ms),
+        }
+    }
+
+    /// Applies ticks that have elapsed. Returns `false` if the effect has ended.
+    fn update(&mut self, time: Duration, mut callback: impl FnMut(Entity, usize)) -> bool
+    {
+        while self.next_tick <= time && self.next_tick <= self.end {
+            callback(self.source, self.damage);
+            self.next_tick += self.tick;
+        }
+        time < self.end
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Copy, Clone)]
+struct Knockback
+{
+    velocity: Vec2,
+    end: Duration,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Component that tracks status effects applied to an entity.
+///
+/// Effects are applied by sending [`StatusEffectEvent`]s.
+#[derive(Component, Debug)]
+pub struct StatusEffects
+{
+    poison: Vec<DamageOverTime>,
+    burn: Option<DamageOverTime>,
+    /// (percent, end time)
+    slow: Option<(f32, Duration)>,
+    stun_end: Option<Duration>,
+    knockback: Option<Knockback>,
+
+    /// Cached
+    speed_multiplier: f32,
+}
+
+impl StatusEffects
+{
+    fn apply(&mut self, effect: StatusEffect, source: Entity, direction: Vec2, time: Duration)
+    {
+        match effect {
+            StatusEffect::Poison { damage, tick_ms, duration_ms } => {
+                if self.poison.len() >= MAX_POISON_STACKS {
+                    self.poison.remove(0);
+                }
+                self.poison
+                    .push(DamageOverTime::new(source, damage, tick_ms, duration_ms, time));
+            }
+            StatusEffect::Burn { damage, tick_ms, duration_ms } => {
+                let new = DamageOverTime::new(source, damage, tick_ms, duration_ms, time);
+                match &mut self.burn {
+                    Some(burn) => {
+                        if damage >= burn.damage {
+                            burn.source = source;
+                            burn.damage = damage;
+                        }
+                        burn.end = burn.end.max(new.end);
+                    }
+                    None => self.burn = Some(new),
+                }
+            }
+            StatusEffect::Slow { percent, duration_ms } => {
+                let percent = percent.clamp(0., 100.);
+                let end = time + Duration::from_millis(duration_ms);
+                match &mut self.slow {
+                    Some((current, current_end)) => {
+                        if percent > *current {
+                            *current = percent;
+                            *current_end = end;
+                        } else if percent == *current {
+                            *current_end = (*current_end).max(end);
+                        }
+                    }
+                    None => self.slow = Some((percent, end)),
+                }
+            }
+            StatusEffect::Stun { duration_ms } => {
+                let end = time + Duration::from_millis(duration_ms);
+                self.stun_end = Some(self.stun_end.map(|e| e.max(end)).unwrap_or(end));
+            }
+            StatusEffect::Knockback { distance, duration_ms } => {
+                let duration = Duration::from_millis(duration_ms.max(1));
+                let direction = direction.normalize_or_zero();
+                self.knockback = Some(Knockback {
+                    velocity: direction * distance / duration.as_secs_f32(),
+                    end: time + duration,
+                });
+            }
+        }
+
+        self.refresh_speed_multiplier(time);
+    }
+
+    fn update(&mut self, time: Duration, mut damage_callback: impl FnMut(Entity, usize))
+    {
+        self.poison
+            .retain_mut(|poison| poison.update(time, &mut damage_callback));
+        if self
+            .burn
+            .as_mut()
+            .map(|burn| !burn.update(time, &mut damage_callback))
+            .unwrap_or(false)
+        {
+            self.burn = None;
+        }
+        if self.slow.map(|(_, end)| end <= time).unwrap_or(false) {
+            self.slow = None;
+        }
+        if self.stun_end.map(|end| end <= time).unwrap_or(false) {
+            self.stun_end = None;
+        }
+        if self.knockback.map(|k| k.end <= time).unwrap_or(false) {
+            self.knockback = None;
+        }
+
+        self.refresh_speed_multiplier(time);
+    }
+
+    fn refresh_speed_multiplier(&mut self, time: Duration)
+    {
+        self.speed_multiplier = if self.stun_end.map(|end| time < end).unwrap_or(false) {
+            0.
+        } else if let Some((percent, _)) = self.slow {
+            1. - percent / 100.
+        } else {
+            1.
+        };
+    }
+
+    /// Gets the tint of the strongest active effect. See [`SpriteTint`].
+    pub fn tint(&self, constants: &GameConstants) -> Option<Color>
+    {
+        if self.stun_end.is_some() {
+            Some(constants.status_tint_stun)
+        } else if self.burn.is_some() {
+            Some(constants.status_tint_burn)
+        } else if !self.poison.is_empty() {
+            Some(constants.status_tint_poison)
+        } else if self.slow.is_some() {
+            Some(constants.status_tint_slow)
+        } else {
+            None
+        }
+    }
+
+    /// Multiplier to apply to movement speed. Equals `0.` when stunned.
+    pub fn speed_multiplier(&self) -> f32
+    {
+        self.speed_multiplier
+    }
+
+    pub fn is_stunned(&self) -> bool
+    {
+        self.stun_end.is_some()
+    }
+}
+
+impl Default for StatusEffects
+{
+    fn default() -> Self
+    {
+        Self {
+            poison: Vec::default(),
+            burn: None,
+            slow: None,
+            stun_end: None,
+            knockback: None,
+            speed_multiplier: 1.,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Event sent to apply a status effect to an entity.
+#[derive(Event, Debug, Copy, Clone)]
+pub struct StatusEffectEvent
+{
+    pub source: Entity,
+    pub target: Entity,
+    pub effect: StatusEffect,
+    /// Location the effect came from. Used to orient knockback.
+    pub origin: Vec2,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct StatusEffectsPlugin;
+
+impl Plugin for StatusEffectsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_type::<StatusEffect>()
+            .add_state_scoped_event::<StatusEffectEvent>(PlayState::Day)
+            .add_systems(Update, update_status_effects.in_set(StatsUpdateSet))
+            .add_systems(Update, apply_knockback.in_set(PlayerUpdateSet))
+            .add_systems(Update, handle_status_effect_events.in_set(DamageSet::DetectDamage));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
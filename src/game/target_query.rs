@@ -0,0 +1,269 @@
+use std::cmp::Ordering;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Orders targets by distance, then by entity so ties resolve deterministically.
+fn cmp_targets(a: &Target, b: &Target) -> Ordering
+{
+    a.distance
+        .total_cmp(&b.distance)
+        .then_with(|| a.entity.cmp(&b.entity))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A mob or prop found by [`TargetQuery`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Target
+{
+    pub entity: Entity,
+    pub location: Vec2,
+    /// Distance from the search origin.
+    pub distance: f32,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// How an ability selects what to aim at.
+#[derive(Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TargetingPolicy
+{
+    /// The `count` nearest mobs in range.
+    Nearest
+    {
+        range: f32, count: usize
+    },
+    /// The mob with the most health in range. Ties go to the closest mob.
+    HighestHealth
+    {
+        range: f32
+    },
+    /// A random mob in range.
+    Random
+    {
+        range: f32
+    },
+    /// The center of the largest group of mobs in range.
+    ///
+    /// Groups are made of all mobs within `cluster_radius` of a mob.
+    DensestCluster
+    {
+        range: f32, cluster_radius: f32
+    },
+}
+
+impl Default for TargetingPolicy
+{
+    fn default() -> Self
+    {
+        Self::Nearest { range: 0., count: 1 }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// System parameter for finding mobs to target with abilities.
+///
+/// Searches are backed by the [`SpatialGrid`], so only mobs near the search origin are visited.
+///
+/// Searches include destructible [`Prop`]s if `include_props` is set.
+#[derive(SystemParam)]
+pub struct TargetQuery<'w, 's>
+{
+    grid: Res<'w, SpatialGrid>,
+    mobs: Query<'w, 's, (&'static Transform, &'static Health), With<Mob>>,
+    props: Query<'w, 's, (&'static Transform, &'static Health), (With<Prop>, Without<Mob>)>,
+}
+
+impl<'w, 's> TargetQuery<'w, 's>
+{
+    fn get(&self, entity: Entity, include_props: bool) -> Option<(&Transform, &Health)>
+    {
+        self.mobs
+            .get(entity)
+            .ok()
+            .or_else(|| self.props.get(entity).ok().filter(|_| include_props))
+    }
+
+    fn for_each_in_range(
+        &self,
+        origin: Vec2,
+        range: f32,
+        include_props: bool,
+        mut callback: impl FnMut(Target, &Health),
+    )
+    {
+        self.grid.for_each_in_radius(origin, range, |entity| {
+            let Some((transform, health)) = self.get(entity, include_props) else { return };
+            // Skip mobs that are dying (e.g. killed earlier this frame).
+            if health.current() == 0 {
+                return;
+            }
+            let location = transform.translation.truncate();
+            let distance = location.distance(origin);
+            if distance > range {
+                return;
+            }
+            callback(Target { entity, location, distance }, health);
+        });
+    }
+
+    /// Gets all mobs in range, sorted by distance.
+    pub fn all_in_range(&self, origin: Vec2, range: f32, include_props: bool) -> Vec<Target>
+    {
+        let mut targets = Vec::default();
+        self.for_each_in_range(origin, range, include_props, |target, _| targets.push(target));
+        targets.sort_unstable_by(cmp_targets);
+        targets
+    }
+
+    /// Gets the nearest mob in range.
+    pub fn nearest(&self, origin: Vec2, range: f32, include_props: bool) -> Option<Target>
+    {
+        self.grid
+            .nearest(origin, range, |entity| {
+                self.get(entity, include_props)
+                    .filter(|(_, health)| health.current() > 0)
+                    .map(|(transform, _)| transform.translation.truncate())
+            })
+            .map(|(entity, location, distance)| Target { entity, location, distance })
+    }
+
+    /// Gets up to `count` of the nearest mobs in range, sorted by distance.
+    pub fn nearest_n(&self, origin: Vec2, range: f32, count: usize, include_props: bool) -> Vec<Target>
+    {
+        if count == 1 {
+            return self
+                .nearest(origin, range, include_props)
+                .into_iter()
+                .collect();
+        }
+        let mut targets = self.all_in_range(origin, range, include_props);
+        targets.truncate(count);
+        targets
+    }
+
+    /// Gets the mob with the most health in range. Ties go to the closest mob.
+    pub fn highest_health_in_range(&self, origin: Vec2, range: f32, include_props: bool) -> Option<Target>
+    {
+        let mut best: Option<(usize, Target)> = None;
+        self.for_each_in_range(origin, range, include_props, |target, health| {
+            let is_better = match &best {
+                None => true,
+                Some((best_health, best_target)) => match health.current().cmp(best_health) {
+                    Ordering::Greater => true,
+                    Ordering::Equal => cmp_targets(&target, best_target) == Ordering::Less,
+                    Ordering::Less => false,
+                },
+            };
+            if is_better {
+                best = Some((health.current(), target));
+            }
+        });
+        best.map(|(_, target)| target)
+    }
+
+    /// Gets a random mob in range.
+    pub fn random_in_range(
+        &self,
+        origin: Vec2,
+        range: f32,
+        include_props: bool,
+        rng: &mut impl Rng,
+    ) -> Option<Target>
+    {
+        let targets = self.all_in_range(origin, range, include_props);
+        if targets.is_empty() {
+            return None;
+        }
+        Some(targets[rng.gen_range(0..targets.len())])
+    }
+
+    /// Gets the center of the largest group of mobs in range, and the number of mobs in that group.
+    ///
+    /// Each mob in range is the seed of a group that contains all mobs within `cluster_radius` of it. Ties go to
+    /// the group whose seed is closest.
+    pub fn densest_cluster(
+        &self,
+        origin: Vec2,
+        range: f32,
+        cluster_radius: f32,
+        include_props: bool,
+    ) -> Option<(Vec2, usize)>
+    {
+        let mut best: Option<(Vec2, usize)> = None;
+        for seed in self.all_in_range(origin, range, include_props) {
+            let mut count = 0;
+            let mut sum = Vec2::ZERO;
+            self.for_each_in_range(seed.location, cluster_radius, include_props, |member, _| {
+                count += 1;
+                sum += member.location;
+            });
+            if count == 0
+                || best
+                    .map(|(_, best_count)| count <= best_count)
+                    .unwrap_or(false)
+            {
+                continue;
+            }
+            best = Some((sum / count as f32, count));
+        }
+        best
+    }
+
+    /// Gets target locations selected by a targeting policy.
+    ///
+    /// Returns an empty list if no mobs were found.
+    pub fn select(
+        &self,
+        origin: Vec2,
+        policy: &TargetingPolicy,
+        include_props: bool,
+        rng: &mut impl Rng,
+    ) -> Vec<Vec2>
+    {
+        match *policy {
+            TargetingPolicy::Nearest { range, count } => self
+                .nearest_n(origin, range, count, include_props)
+                .iter()
+                .map(|target| target.location)
+                .collect(),
+            TargetingPolicy::HighestHealth { range } => self
+                .highest_health_in_range(origin, range, include_props)
+                .map(|target| target.location)
+                .into_iter()
+                .collect(),
+            TargetingPolicy::Random { range } => self
+                .random_in_range(origin, range, include_props, rng)
+                .map(|target| target.location)
+                .into_iter()
+                .collect(),
+            TargetingPolicy::DensestCluster { range, cluster_radius } => self
+                .densest_cluster(origin, range, cluster_radius, include_props)
+                .map(|(location, _)| location)
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct TargetQueryPlugin;
+
+impl Plugin for TargetQueryPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_type::<TargetingPolicy>();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
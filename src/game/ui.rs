@@ -59,7 +59,7 @@ fn game_hud_slot_builder<'a>(
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn spawn_game_hud(mut c: Commands, mut s: ResMut<SceneLoader>, constants: ReactRes<GameConstants>)
+fn spawn_game_hud(mut c: Commands, mut s: ResMut<SceneLoader>, slots: Res<PowerupSlots>)
 {
     let file = LoadableRef::from_file("ui.game_hud");
     let scene = file.e("scene");
@@ -92,14 +92,31 @@ fn spawn_game_hud(mut c: Commands, mut s: ResMut<SceneLoader>, constants: ReactR
             });
         });
 
+        l.edit("boss", |l| {
+            let name_entity = l.get_entity("name").unwrap();
+            let fill_entity = l.get_entity("bar::fill").unwrap();
+            l.update_on(resource_mutation::<ActiveBoss>(), |id| {
+                move |mut c: Commands, mut e: TextEditor, boss: ReactRes<ActiveBoss>| {
+                    let Some(name) = &boss.name else {
+                        c.entity(id).insert_reactive(DisplayControl::Hide);
+                        return;
+                    };
+                    c.entity(id).insert_reactive(DisplayControl::Display);
+                    write_text!(e, name_entity, "{}", name);
+                    c.entity(fill_entity)
+                        .insert_derived(Width(Val::Percent(boss.health_percent())));
+                }
+            });
+        });
+
         l.edit("footer::passives::slots", |l| {
-            for i in 0..constants.num_passive_slots {
+            for i in 0..slots.passive {
                 game_hud_slot_builder(l, &file, i, AbilityType::Passive);
             }
         });
 
         l.edit("footer::actives::slots", |l| {
-            for i in 0..constants.num_passive_slots {
+            for i in 0..slots.active {
                 game_hud_slot_builder(l, &file, i, AbilityType::Active);
             }
         });
@@ -113,10 +130,14 @@ fn spawn_power_up_ui(
     mut time: ResMut<Time<Virtual>>,
     mut rng: ResMut<GameRng>,
     constants: ReactRes<GameConstants>,
+    slots: Res<PowerupSlots>,
+    mut rerolls: ResMut<PowerupRerolls>,
     player_powerups: ReactRes<PlayerPowerups>,
     powerup_bank: Res<PowerupBank>,
     mut s: ResMut<SceneLoader>,
     mut powerups: ResMut<BufferedPowerUps>,
+    auto_select: Option<Res<PowerupAutoSelect>>,
+    mut replay: Option<ResMut<DayReplay>>,
 )
 {
     let Some(powerup_source) = powerups.current_powerup() else {
@@ -125,12 +146,43 @@ fn spawn_power_up_ui(
         return;
     };
 
-    // Pause time now that we're spawning a power-up sequence.
-    time.pause();
-
     // Generate power-up options for the player.
-    let options = get_powerup_options(&constants, &mut rng, powerup_source, &player_powerups, &powerup_bank);
+    let mut generate_options = || {
+        get_powerup_options(
+            &constants,
+            &mut rng,
+            &slots,
+            powerup_source,
+            &player_powerups,
+            &powerup_bank,
+        )
+    };
+    let mut options = generate_options();
     debug_assert!(options.len() > 0);
+
+    // Apply recorded rerolls so the replayed options match the recording.
+    if let Some(replay) = &replay {
+        for _ in 0..replay.next_powerup_rerolls() {
+            if !rerolls.spend() {
+                tracing::warn!("replay desync: recorded power-up reroll is not available");
+            }
+            options = generate_options();
+        }
+    }
+
+    // Resolve the power-up immediately if it was recorded or there is no player to make a choice.
+    let selection = replay
+        .as_mut()
+        .and_then(|replay| replay.next_powerup(&options))
+        .or_else(|| auto_select.and_then(|_| options.first().cloned()));
+    if let Some(option) = selection {
+        option.apply(&mut c);
+        powerups.end_handling_powerup();
+        return;
+    }
+
+    // Pause time now that we're spawning a power-up sequence.
+    time.pause();
     let is_filler = options
         .iter()
         .any(|o| matches!(o, PowerupOption::Filler(..)));
@@ -237,17 +289,117 @@ fn spawn_power_up_ui(
         if is_filler {
             l.load_scene(file.e("filler_notification"), |_| {});
         }
+
+        // Rerolling replaces this scene with a new set of options for the same power-up.
+        if rerolls.remaining() > 0 {
+            l.load_scene(file.e("reroll_button"), |l| {
+                let remaining = rerolls.remaining();
+                l.edit("button::text", |l| {
+                    l.update_on((), |id| {
+                        move |mut e: TextEditor| {
+                            write_text!(e, id, "Reroll ({})", remaining);
+                        }
+                    });
+                });
+                l.edit("button", |l| {
+                    l.on_pressed(
+                        move |mut c: Commands,
+                              mut rerolls: ResMut<PowerupRerolls>,
+                              recorder: Option<ResMut<DayRecorder>>| {
+                            if !rerolls.spend() {
+                                return;
+                            }
+                            if let Some(mut recorder) = recorder {
+                                recorder.record_powerup_reroll();
+                            }
+                            c.entity(scene_id).despawn_recursive();
+                            c.react().broadcast(PlayerPowerUp);
+                        },
+                    );
+                });
+            });
+        }
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn day_stat_row_builder<'a>(
+    l: &mut LoadedScene<'a, '_, UiBuilder<'a, Entity>>,
+    file: &LoadableRef,
+    name: String,
+    value: String,
+)
+{
+    l.load_scene(file.e("stat_row"), |l| {
+        l.edit("name", |l| {
+            l.update_on((), |id| {
+                move |mut e: TextEditor| {
+                    write_text!(e, id, "{}", name);
+                }
+            });
+        });
+        l.edit("value", |l| {
+            l.update_on((), |id| {
+                move |mut e: TextEditor| {
+                    write_text!(e, id, "{}", value);
+                }
+            });
+        });
+    });
+}
+
+/// Fills in the day's stats on the day result screen.
+fn day_stats_builder<'a>(l: &mut LoadedScene<'a, '_, UiBuilder<'a, Entity>>, file: &LoadableRef, stats: &RunStats)
+{
+    l.edit("window::stats::summary::text", |l| {
+        let secs = stats.time_survived.as_secs();
+        let summary = format!(
+            "Time survived: {}:{:02}\nLevel reached: {}\nKills: {}\nDamage dealt: {}\nDamage taken: {}\n\
+            Exp collected: {}\nKarma collected: {}",
+            secs / 60,
+            secs % 60,
+            stats.level,
+            stats.total_kills(),
+            stats.total_damage(),
+            stats.damage_taken,
+            stats.exp_collected,
+            stats.karma_collected
+        );
+        l.update_on((), |id| {
+            move |mut e: TextEditor| {
+                write_text!(e, id, "{}", summary);
+            }
+        });
+    });
+
+    l.edit("window::stats::kills::list", |l| {
+        for (name, kills) in stats.sorted_kills() {
+            day_stat_row_builder(l, file, name.into(), kills.to_string());
+        }
+    });
+
+    // Per-ability breakdown of damage dealt.
+    l.edit("window::stats::damage::list", |l| {
+        let total = stats.total_damage().max(1) as f32;
+        for (name, damage) in stats.sorted_ability_damage() {
+            let percent = ((damage as f32) * 100. / total).round();
+            day_stat_row_builder(l, file, name.into(), format!("{} ({}%)", damage, percent));
+        }
     });
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn spawn_day_failed_ui(mut c: Commands, mut s: ResMut<SceneLoader>)
+fn spawn_day_failed_ui(mut c: Commands, mut s: ResMut<SceneLoader>, stats: Res<RunStats>)
 {
-    let scene = LoadableRef::new("ui.day_result", "failure_scene");
+    let file = LoadableRef::from_file("ui.day_result");
+    let scene = file.e("failure_scene");
     c.ui_builder(UiRoot).load_scene(&mut s, scene, |l| {
         l.despawn_on_broadcast::<GameDayStart>();
 
+        day_stats_builder(l, &file, &stats);
+
         l.edit("window::today_again_button", |l| {
             l.on_pressed(|mut c: Commands| {
                 c.set_state(GameState::DayStart);
@@ -258,12 +410,15 @@ fn spawn_day_failed_ui(mut c: Commands, mut s: ResMut<SceneLoader>)
 
 //-------------------------------------------------------------------------------------------------------------------
 
-fn spawn_day_survived_ui(mut c: Commands, mut s: ResMut<SceneLoader>)
+fn spawn_day_survived_ui(mut c: Commands, mut s: ResMut<SceneLoader>, stats: Res<RunStats>)
 {
-    let scene = LoadableRef::new("ui.day_result", "success_scene");
+    let file = LoadableRef::from_file("ui.day_result");
+    let scene = file.e("success_scene");
     c.ui_builder(UiRoot).load_scene(&mut s, scene, |l| {
         l.despawn_on_broadcast::<GameDayStart>();
 
+        day_stats_builder(l, &file, &stats);
+
         l.edit("window::tomorrow_button", |l| {
             l.on_pressed(|mut c: Commands, mut day: ReactResMut<Day>| {
                 day.get_mut(&mut c).increment();
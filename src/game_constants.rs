@@ -33,7 +33,13 @@ pub struct GameConstants
     pub collectable_healthpack_size: Vec2,
 
     pub mob_attraction_offset: f32,
+    /// Mobs closer than this distance push each other apart.
+    pub mob_separation_radius: f32,
+    /// Max speed (in transform units per second) that mobs push each other apart.
+    pub mob_separation_strength: f32,
     pub collider_mob_stop_distance: f32,
+    /// Tint applied to chargers and bosses while they telegraph a charge.
+    pub charge_telegraph_tint: Color,
 
     pub player_size: Vec2,
     pub player_standing_animation: String,
@@ -42,8 +48,21 @@ pub struct GameConstants
     pub player_run_speed_tps: usize,
     pub player_base_hp: usize,
     pub player_base_armor: usize,
+    /// Percent chance for player damage to be a critical hit.
+    pub player_base_crit_chance: usize,
+    /// Percent bonus damage of player critical hits.
+    pub player_base_crit_damage: usize,
     pub player_exp_start: usize,
     pub player_exp_rate: usize,
+    /// How long the player ignores damage after being hit. Set to zero to disable.
+    pub player_invulnerability_ms: u64,
+    /// Duration of each on/off phase of the player's sprite blinking while invulnerable.
+    pub player_invulnerability_blink_ms: u64,
+    /// Sprite alpha during the 'off' phase of blinking.
+    pub player_invulnerability_blink_alpha: f32,
+    /// Distance the player is knocked away from damage sources when hit. Set to zero to disable.
+    pub player_hit_knockback_distance: f32,
+    pub player_hit_knockback_ms: u64,
 
     pub starting_powerup: String,
     pub max_powerup_level: usize,
@@ -66,6 +85,25 @@ pub struct GameConstants
     pub hp_bar_filled_color: Color,
     pub hp_bar_empty_color: Color,
 
+    pub status_tint_poison: Color,
+    pub status_tint_burn: Color,
+    pub status_tint_slow: Color,
+    pub status_tint_stun: Color,
+
+    pub damage_number_font: String,
+    pub damage_number_font_size: f32,
+    /// Scale applied to critical hit numbers.
+    pub damage_number_crit_scale: f32,
+    pub damage_number_lifetime_ms: u64,
+    /// How far numbers rise over their lifetime.
+    pub damage_number_rise_distance: f32,
+    /// Damage dealt by the player.
+    pub damage_number_color: Color,
+    pub damage_number_crit_color: Color,
+    /// Damage taken by the player.
+    pub damage_number_player_color: Color,
+    pub damage_number_heal_color: Color,
+
     pub background_tile_texture: String,
     pub background_tile_configs: Vec<TileConfig>,
     /// The total map area in number of tiles (rectangular). todo: consider making this programmatic per-day?
@@ -79,6 +117,19 @@ pub struct GameConstants
     pub boundary_corner_texture: String,
     pub boundary_width: f32,
     pub boundary_length: f32,
+
+    /// Size of each cell in the `SpatialGrid`.
+    pub spatial_grid_cell_size: f32,
+    /// Padding added to `SpatialGrid` queries, to catch entities that moved after the grid was updated.
+    pub spatial_grid_margin: f32,
+
+    /// Size of each cell in the `ObstacleFlowField` used to path mobs around obstacles.
+    pub obstacle_path_cell_size: f32,
+    /// Padding around obstacles when marking flow field cells as blocked, so mobs don't clip obstacle corners.
+    pub obstacle_path_padding: f32,
+
+    /// Minimum distance between the player and the edge of hazards spawned near the player.
+    pub hazard_player_clear_radius: f32,
 }
 
 impl Command for GameConstants
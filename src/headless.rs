@@ -0,0 +1,205 @@
+use std::time::Duration;
+
+use bevy::app::{AppExit, ScheduleRunnerPlugin};
+use bevy::asset::AssetMetaCheck;
+use bevy::prelude::*;
+use bevy::render::settings::WgpuSettings;
+use bevy::render::RenderPlugin;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::ExitCondition;
+use bevy::winit::WinitPlugin;
+use bevy_cobweb::prelude::*;
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn start_day(mut c: Commands)
+{
+    c.set_state(GameState::Play);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn log_player_survived(day: ReactRes<Day>, clock: Res<GameClock>)
+{
+    tracing::info!("headless: survived day {} ({:?} elapsed)", day.get(), clock.elapsed);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn log_player_died(day: ReactRes<Day>, clock: Res<GameClock>)
+{
+    tracing::info!("headless: died on day {} ({:?} elapsed)", day.get(), clock.elapsed);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn exit_app(mut exit: EventWriter<AppExit>)
+{
+    exit.send(AppExit::Success);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn is_in_day(w: &World) -> bool
+{
+    w.get_resource::<State<PlayState>>()
+        .map(|s| *s.get() == PlayState::Day)
+        .unwrap_or(false)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Runs the game without a window or GPU.
+///
+/// Bevy's default plugins are added with windowing disabled and without a render backend, so all asset types
+/// remain registered and the `.caf.json` data loads normally. Use this in place of [`AppPlugin`].
+///
+/// - The spawn viewport is taken from [`VirtualViewport`] instead of the primary window.
+/// - Each app update advances time by `delta`, so `GameClock` steps deterministically.
+/// - Power-up offers are resolved automatically via [`PowerupAutoSelect`].
+#[derive(Debug, Clone)]
+pub struct HeadlessAppPlugin
+{
+    /// Size of the virtual viewport, equivalent to the window size in logical pixels.
+    pub viewport: Vec2,
+    /// Time that elapses in every app update.
+    ///
+    /// Must be smaller than `Time<Virtual>`'s max delta (250ms by default).
+    pub delta: Duration,
+    /// Seed for [`GameRng`].
+    pub seed: u64,
+    /// If true then days will start automatically when entering `GameState::DayStart`.
+    pub auto_start_day: bool,
+    /// If true then the app will exit when entering `PlayState::DayOver`.
+    pub exit_on_day_over: bool,
+}
+
+impl Default for HeadlessAppPlugin
+{
+    fn default() -> Self
+    {
+        Self {
+            viewport: Vec2 { x: 1280., y: 720. },
+            delta: Duration::from_secs_f32(1. / 60.),
+            seed: 0,
+            auto_start_day: true,
+            exit_on_day_over: false,
+        }
+    }
+}
+
+impl Plugin for HeadlessAppPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                })
+                .set(RenderPlugin {
+                    render_creation: WgpuSettings { backends: None, ..default() }.into(),
+                    ..default()
+                })
+                .set(ImagePlugin::default_nearest())
+                .set(AssetPlugin { meta_check: AssetMetaCheck::Never, ..default() })
+                .disable::<WinitPlugin>(),
+        )
+        .add_plugins(ScheduleRunnerPlugin::run_loop(Duration::ZERO))
+        .add_plugins(GameCorePlugin)
+        // Overwrite the time-seeded rng inserted by UtilsPlugin.
+        .insert_resource(GameRng::new(self.seed))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(self.delta))
+        .insert_resource(VirtualViewport(self.viewport))
+        .init_resource::<PowerupAutoSelect>()
+        .react(|rc| rc.on_persistent(broadcast::<PlayerSurvived>(), log_player_survived))
+        .react(|rc| rc.on_persistent(broadcast::<PlayerDied>(), log_player_died));
+
+        if self.auto_start_day {
+            app.add_systems(OnEnter(GameState::DayStart), start_day);
+        }
+        if self.exit_on_day_over {
+            app.add_systems(OnEnter(PlayState::DayOver), exit_app);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub trait HeadlessAppExt
+{
+    /// Updates the app until `predicate` returns true or `max_updates` updates have run.
+    ///
+    /// Returns `true` if the predicate was satisfied.
+    fn update_until(&mut self, max_updates: usize, predicate: impl Fn(&World) -> bool) -> bool;
+
+    /// Updates the app until `GameClock` has advanced by at least `duration`.
+    ///
+    /// Stops early if the app leaves `PlayState::Day`, since the clock doesn't advance outside of days. Returns an
+    /// error if the clock didn't reach its target within `max_updates` updates (e.g. because `Time<Virtual>` is
+    /// paused).
+    fn advance_game_clock(&mut self, duration: Duration, max_updates: usize) -> Result<&mut Self, String>;
+}
+
+impl HeadlessAppExt for App
+{
+    fn update_until(&mut self, max_updates: usize, predicate: impl Fn(&World) -> bool) -> bool
+    {
+        for _ in 0..max_updates {
+            if predicate(self.world()) {
+                return true;
+            }
+            self.update();
+        }
+        predicate(self.world())
+    }
+
+    fn advance_game_clock(&mut self, duration: Duration, max_updates: usize) -> Result<&mut Self, String>
+    {
+        let target = self.world().resource::<GameClock>().elapsed + duration;
+        let reached = self.update_until(max_updates, |w| {
+            !is_in_day(w) || w.resource::<GameClock>().elapsed >= target
+        });
+        if !reached {
+            return Err(format!(
+                "game clock didn't reach {:?} after {} updates (stopped at {:?})",
+                target,
+                max_updates,
+                self.world().resource::<GameClock>().elapsed
+            ));
+        }
+        Ok(self)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn headless_day_spawns_mobs()
+    {
+        let mut app = App::new();
+        app.add_plugins(HeadlessAppPlugin::default());
+        app.finish();
+        app.cleanup();
+
+        // Assets load in the background, so this can take many updates.
+        assert!(app.update_until(100_000, is_in_day), "day didn't start");
+        app.advance_game_clock(Duration::from_secs(3), 1_000)
+            .unwrap();
+
+        assert!(app.world().resource::<GameClock>().elapsed >= Duration::from_secs(3));
+        let mut mobs = app.world_mut().query_filtered::<(), With<Mob>>();
+        assert!(mobs.iter(app.world()).count() > 0, "no mobs spawned");
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -7,8 +7,10 @@ mod dev;
 mod events;
 mod game;
 mod game_constants;
+mod headless;
 mod meta;
 mod plugin;
+mod replay;
 mod settings;
 mod utils;
 
@@ -19,12 +21,52 @@ pub use dev::*;
 pub use events::*;
 pub use game::*;
 pub use game_constants::*;
+pub use headless::*;
 pub use meta::*;
 pub use plugin::*;
+pub use replay::*;
 pub use settings::*;
 pub use utils::*;
 
+fn arg_value(name: &str) -> Option<String>
+{
+    let mut args = std::env::args().skip_while(|arg| arg != name);
+    args.next()?;
+    args.next()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 fn main()
 {
-    bevy::app::App::new().add_plugins(AppPlugin).run();
+    let mut app = bevy::app::App::new();
+
+    // Simulate a single day without a window: `cargo run -- --headless`
+    match std::env::args().any(|arg| arg == "--headless") {
+        true => app.add_plugins(HeadlessAppPlugin { exit_on_day_over: true, ..Default::default() }),
+        false => app.add_plugins(AppPlugin),
+    };
+
+    // Use a custom save file: `cargo run -- --save save.json`
+    // - The windowed app saves to `save.json` by default. Headless apps only save if a path is set.
+    if let Some(path) = arg_value("--save") {
+        app.insert_resource(SaveFile::new(path));
+    }
+
+    // Record days to a file: `cargo run -- --record day.json`
+    if let Some(path) = arg_value("--record") {
+        app.insert_resource(DayRecorder::new(path));
+    }
+
+    // Replay a recorded day: `cargo run -- --replay day.json`
+    if let Some(path) = arg_value("--replay") {
+        match DayRecording::load(&path) {
+            Ok(recording) => {
+                app.insert_resource(DayReplay::new(recording));
+            }
+            Err(err) => tracing::error!("failed loading day recording {:?}: {}", path, err),
+        }
+    }
+
+    app.run();
 }
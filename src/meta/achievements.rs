@@ -0,0 +1,455 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const ACHIEVEMENT_TOAST_SECS: f32 = 4.0;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Counts kills of the given mobs. Counts all mobs if `mobs` is empty.
+fn count_kills(kills: &HashMap<String, usize>, mobs: &[String]) -> usize
+{
+    kills
+        .iter()
+        .filter(|(name, _)| mobs.is_empty() || mobs.contains(name))
+        .map(|(_, count)| *count)
+        .sum()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Completes achievements whose conditions are met and grants their rewards.
+fn complete_achievements(
+    c: &mut Commands,
+    database: &AchievementDatabase,
+    progress: &mut ReactResMut<AchievementProgress>,
+    unlocks: &mut ReactResMut<Unlocks>,
+    toasts: &mut AchievementToasts,
+    is_met: impl Fn(&AchievementCondition) -> bool,
+)
+{
+    for achievement in database.iter() {
+        if progress.is_completed(&achievement.name) || !is_met(&achievement.condition) {
+            continue;
+        }
+
+        tracing::info!("completed achievement {:?}", achievement.name);
+        progress.get_mut(c).complete(&achievement.name);
+        for reward in achievement.rewards.iter() {
+            match reward {
+                AchievementReward::UnlockPowerup(powerup) => {
+                    unlocks.get_mut(c).unlock(powerup.clone());
+                }
+            }
+        }
+        toasts.queue.push_back(achievement.name.clone());
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Locks power-ups that are achievement rewards until they are unlocked.
+///
+/// Runs when leaving day start so unlocks earned during a day take effect on the next day.
+fn sync_powerup_locks(
+    database: Res<AchievementDatabase>,
+    unlocks: ReactRes<Unlocks>,
+    mut bank: ResMut<PowerupBank>,
+)
+{
+    for powerup in database.reward_powerups() {
+        bank.set_locked(powerup, !unlocks.is_unlocked(powerup));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn check_kill_achievements(
+    mut c: Commands,
+    mut deaths: EventReader<EntityDeath>,
+    stats: Res<RunStats>,
+    database: Res<AchievementDatabase>,
+    mut progress: ReactResMut<AchievementProgress>,
+    mut unlocks: ReactResMut<Unlocks>,
+    mut toasts: ResMut<AchievementToasts>,
+)
+{
+    if deaths.read().count() == 0 {
+        return;
+    }
+
+    // Kills from the current day are added to the progress when the day ends.
+    let past_kills = progress.kills.clone();
+    complete_achievements(
+        &mut c,
+        &database,
+        &mut progress,
+        &mut unlocks,
+        &mut toasts,
+        |condition| {
+            let AchievementCondition::Kills { mobs, count } = condition else { return false };
+            count_kills(&past_kills, mobs) + count_kills(&stats.kills, mobs) >= *count
+        },
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn check_survival_achievements(
+    mut c: Commands,
+    replay: Option<Res<DayReplay>>,
+    day: ReactRes<Day>,
+    stats: Res<RunStats>,
+    database: Res<AchievementDatabase>,
+    mut progress: ReactResMut<AchievementProgress>,
+    mut unlocks: ReactResMut<Unlocks>,
+    mut toasts: ResMut<AchievementToasts>,
+)
+{
+    if replay.is_some() {
+        return;
+    }
+
+    complete_achievements(
+        &mut c,
+        &database,
+        &mut progress,
+        &mut unlocks,
+        &mut toasts,
+        |condition| match condition {
+            AchievementCondition::SurviveDay(min_day) => day.get() >= *min_day,
+            AchievementCondition::SurviveDayUntouched(min_day) => day.get() >= *min_day && stats.damage_taken == 0,
+            _ => false,
+        },
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn check_powerup_achievements(
+    mut c: Commands,
+    replay: Option<Res<DayReplay>>,
+    constants: ReactRes<GameConstants>,
+    player_powerups: ReactRes<PlayerPowerups>,
+    database: Res<AchievementDatabase>,
+    mut progress: ReactResMut<AchievementProgress>,
+    mut unlocks: ReactResMut<Unlocks>,
+    mut toasts: ResMut<AchievementToasts>,
+)
+{
+    if replay.is_some() {
+        return;
+    }
+
+    complete_achievements(
+        &mut c,
+        &database,
+        &mut progress,
+        &mut unlocks,
+        &mut toasts,
+        |condition| {
+            let AchievementCondition::PowerupMaxLevel(powerup) = condition else { return false };
+            player_powerups.get(powerup) >= constants.max_powerup_level
+        },
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn check_karma_achievements(
+    mut c: Commands,
+    replay: Option<Res<DayReplay>>,
+    karma: ReactRes<Karma>,
+    database: Res<AchievementDatabase>,
+    mut progress: ReactResMut<AchievementProgress>,
+    mut unlocks: ReactResMut<Unlocks>,
+    mut toasts: ResMut<AchievementToasts>,
+)
+{
+    if replay.is_some() {
+        return;
+    }
+
+    complete_achievements(
+        &mut c,
+        &database,
+        &mut progress,
+        &mut unlocks,
+        &mut toasts,
+        |condition| {
+            let AchievementCondition::Karma(total) = condition else { return false };
+            karma.total() >= *total
+        },
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Adds kills from the day that ended to the achievement progress.
+fn record_day_kills(mut c: Commands, stats: Res<RunStats>, mut progress: ReactResMut<AchievementProgress>)
+{
+    if stats.kills.is_empty() {
+        return;
+    }
+    progress.get_mut(&mut c).add_kills(&stats.kills);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn update_achievement_toasts(
+    mut c: Commands,
+    mut s: ResMut<SceneLoader>,
+    time: Res<Time<Real>>,
+    database: Res<AchievementDatabase>,
+    mut toasts: ResMut<AchievementToasts>,
+)
+{
+    // Time may be paused (e.g. in menus), so toasts use real time.
+    if let Some((entity, timer)) = &mut toasts.current {
+        timer.tick(time.delta());
+        if !timer.finished() {
+            return;
+        }
+        c.entity(*entity).despawn_recursive();
+        toasts.current = None;
+    }
+
+    let Some(name) = toasts.queue.pop_front() else { return };
+    let Some(achievement) = database.get(&name) else {
+        tracing::error!("failed showing achievement toast for {:?}; achievement is unknown", name);
+        return;
+    };
+
+    let description = achievement.description.clone();
+    let rewards = achievement
+        .rewards
+        .iter()
+        .map(|reward| match reward {
+            AchievementReward::UnlockPowerup(powerup) => format!("Unlocked: {}", powerup),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut toast_entity = Entity::PLACEHOLDER;
+    let scene = LoadableRef::new("ui.achievement_toast", "scene");
+    c.ui_builder(UiRoot).load_scene(&mut s, scene, |l| {
+        toast_entity = l.id();
+
+        l.edit("toast::name", |l| {
+            l.update_on((), |id| {
+                move |mut e: TextEditor| {
+                    write_text!(e, id, "{}", name);
+                }
+            });
+        });
+        l.edit("toast::description", |l| {
+            l.update_on((), |id| {
+                move |mut e: TextEditor| {
+                    write_text!(e, id, "{}", description);
+                }
+            });
+        });
+        l.edit("toast::rewards", |l| {
+            l.update_on((), |id| {
+                move |mut e: TextEditor| {
+                    write_text!(e, id, "{}", rewards);
+                }
+            });
+        });
+    });
+
+    toasts.current = Some((
+        toast_entity,
+        Timer::from_seconds(ACHIEVEMENT_TOAST_SECS, TimerMode::Once),
+    ));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Condition for completing an achievement.
+#[derive(Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AchievementCondition
+{
+    /// Kill `count` mobs across all days played. `mobs` are names in the `MobDatabase`. Counts all mobs if `mobs`
+    /// is empty.
+    Kills
+    {
+        mobs: Vec<String>, count: usize
+    },
+    /// Survive a day at or after the given day.
+    SurviveDay(usize),
+    /// Survive a day at or after the given day without taking damage.
+    SurviveDayUntouched(usize),
+    /// Reach the max level of a power-up.
+    PowerupMaxLevel(String),
+    /// Have at least this much karma.
+    Karma(usize),
+}
+
+impl Default for AchievementCondition
+{
+    fn default() -> Self
+    {
+        Self::SurviveDay(1)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AchievementReward
+{
+    /// Lets a power-up be offered to the player. Power-ups that are rewards are locked until unlocked.
+    UnlockPowerup(String),
+}
+
+impl Default for AchievementReward
+{
+    fn default() -> Self
+    {
+        Self::UnlockPowerup(String::default())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Achievement
+{
+    pub name: String,
+    pub description: String,
+    pub condition: AchievementCondition,
+    #[reflect(default)]
+    pub rewards: Vec<AchievementReward>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Achievements the player can complete. Completed achievements are tracked in [`AchievementProgress`].
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AchievementDatabase
+{
+    achievements: Vec<Achievement>,
+}
+
+impl AchievementDatabase
+{
+    pub fn get(&self, name: &str) -> Option<&Achievement>
+    {
+        self.achievements.iter().find(|a| a.name == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Achievement>
+    {
+        self.achievements.iter()
+    }
+
+    /// Gets all power-ups unlocked by achievements.
+    pub fn reward_powerups(&self) -> impl Iterator<Item = &str>
+    {
+        self.achievements
+            .iter()
+            .flat_map(|a| a.rewards.iter())
+            .map(|reward| match reward {
+                AchievementReward::UnlockPowerup(powerup) => powerup.as_str(),
+            })
+    }
+}
+
+impl Command for AchievementDatabase
+{
+    fn apply(self, w: &mut World)
+    {
+        w.insert_resource(self);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Progress toward achievements. Progress is kept for the rest of the game.
+#[derive(ReactResource, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AchievementProgress
+{
+    completed: HashSet<String>,
+    /// [ mob name : kills ] from days that ended.
+    kills: HashMap<String, usize>,
+}
+
+impl AchievementProgress
+{
+    pub fn is_completed(&self, name: &str) -> bool
+    {
+        self.completed.contains(name)
+    }
+
+    /// Gets kills of the given mobs from days that ended. Counts all mobs if `mobs` is empty.
+    pub fn kills(&self, mobs: &[String]) -> usize
+    {
+        count_kills(&self.kills, mobs)
+    }
+
+    fn complete(&mut self, name: impl Into<String>)
+    {
+        self.completed.insert(name.into());
+    }
+
+    fn add_kills(&mut self, kills: &HashMap<String, usize>)
+    {
+        for (name, count) in kills.iter() {
+            *self.kills.entry(name.clone()).or_default() += count;
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Achievement toasts waiting to be displayed. Toasts are displayed one at a time.
+#[derive(Resource, Default, Debug)]
+pub struct AchievementToasts
+{
+    /// Names of completed achievements.
+    queue: VecDeque<String>,
+    current: Option<(Entity, Timer)>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Achievements are not tracked while replaying a day.
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_command::<AchievementDatabase>()
+            .init_resource::<AchievementDatabase>()
+            .init_react_resource::<AchievementProgress>()
+            .init_resource::<AchievementToasts>()
+            .add_systems(OnExit(GameState::DayStart), sync_powerup_locks)
+            .add_systems(
+                Update,
+                check_kill_achievements
+                    .after(track_kills)
+                    .in_set(DamageSet::DetectDamage)
+                    .run_if(not(resource_exists::<DayReplay>)),
+            )
+            .add_systems(
+                OnEnter(PlayState::DayOver),
+                record_day_kills.run_if(not(resource_exists::<DayReplay>)),
+            )
+            .add_systems(Update, update_achievement_toasts)
+            .react(|rc| rc.on_persistent(broadcast::<PlayerSurvived>(), check_survival_achievements))
+            .react(|rc| rc.on_persistent(resource_mutation::<PlayerPowerups>(), check_powerup_achievements))
+            .react(|rc| rc.on_persistent(resource_mutation::<Karma>(), check_karma_achievements));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -47,6 +47,13 @@ impl Karma
         true
     }
 
+    /// Replaces all karma with `total` banked karma.
+    pub fn set_total(&mut self, total: usize)
+    {
+        self.total = total;
+        self.day_collected = 0;
+    }
+
     pub fn day_collected(&self) -> usize
     {
         self.day_collected
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use bevy_cobweb_ui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sets up power-up limits for the day from purchased karma upgrades.
+fn apply_karma_powerup_upgrades(
+    constants: ReactRes<GameConstants>,
+    shop: Res<KarmaShop>,
+    upgrades: ReactRes<KarmaUpgrades>,
+    mut slots: ResMut<PowerupSlots>,
+    mut rerolls: ResMut<PowerupRerolls>,
+)
+{
+    *slots = PowerupSlots {
+        passive: constants.num_passive_slots + shop.bonus(KarmaUpgradeEffect::PassiveSlots, &upgrades),
+        active: constants.num_active_slots + shop.bonus(KarmaUpgradeEffect::ActiveSlots, &upgrades),
+    };
+    *rerolls = PowerupRerolls::new(shop.bonus(KarmaUpgradeEffect::Rerolls, &upgrades));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// System that buys the next rank of a karma upgrade if the player has enough karma.
+pub fn purchase_karma_upgrade(
+    In(name): In<String>,
+    mut c: Commands,
+    shop: Res<KarmaShop>,
+    mut karma: ReactResMut<Karma>,
+    mut upgrades: ReactResMut<KarmaUpgrades>,
+)
+{
+    let Some(upgrade) = shop.get(&name) else {
+        tracing::error!("failed purchasing karma upgrade {:?}; upgrade is unknown", name);
+        return;
+    };
+    let Some(cost) = upgrade.next_cost(upgrades.rank(&name)) else { return };
+    if karma.total() < cost {
+        return;
+    }
+    if !karma.get_mut(&mut c).spend(cost) {
+        return;
+    }
+    upgrades.get_mut(&mut c).add_rank(&name);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// What a karma upgrade improves.
+#[derive(Reflect, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum KarmaUpgradeEffect
+{
+    /// Adds to the player's base stat, in the same units as the matching passive's bonuses.
+    Stat(Passive),
+    /// Adds passive power-up slots.
+    PassiveSlots,
+    /// Adds active power-up slots.
+    ActiveSlots,
+    /// Adds power-up rerolls for each day.
+    Rerolls,
+}
+
+impl Default for KarmaUpgradeEffect
+{
+    fn default() -> Self
+    {
+        Self::Stat(Passive::default())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KarmaUpgrade
+{
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    pub effect: KarmaUpgradeEffect,
+    /// Karma cost of each rank. The max rank equals the number of costs.
+    pub costs: Vec<usize>,
+    /// Total bonus at each rank. Uses the last bonus if there are fewer bonuses than costs.
+    pub bonuses: Vec<usize>,
+}
+
+impl KarmaUpgrade
+{
+    pub fn max_rank(&self) -> usize
+    {
+        self.costs.len()
+    }
+
+    /// Gets the cost of the rank after `rank`. Returns `None` if `rank` is the max rank.
+    pub fn next_cost(&self, rank: usize) -> Option<usize>
+    {
+        self.costs.get(rank).cloned()
+    }
+
+    /// Gets the bonus for a given rank. Returns 0 for rank 0.
+    pub fn bonus(&self, rank: usize) -> usize
+    {
+        if rank == 0 {
+            return 0;
+        }
+        self.bonuses
+            .get(rank - 1)
+            .or_else(|| self.bonuses.last())
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Upgrades that can be bought with karma between days. Upgrades are displayed in the order they are defined.
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KarmaShop
+{
+    upgrades: Vec<KarmaUpgrade>,
+}
+
+impl KarmaShop
+{
+    pub fn get(&self, name: &str) -> Option<&KarmaUpgrade>
+    {
+        self.upgrades.iter().find(|u| u.name == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &KarmaUpgrade>
+    {
+        self.upgrades.iter()
+    }
+
+    /// Gets the total bonus from purchased upgrades with the given effect.
+    pub fn bonus(&self, effect: KarmaUpgradeEffect, upgrades: &KarmaUpgrades) -> usize
+    {
+        self.upgrades
+            .iter()
+            .filter(|u| u.effect == effect)
+            .map(|u| u.bonus(upgrades.rank(&u.name)))
+            .sum()
+    }
+
+    /// Gets the total stat bonus from purchased upgrades for a passive stat.
+    pub fn stat_bonus(&self, passive: Passive, upgrades: &KarmaUpgrades) -> usize
+    {
+        self.bonus(KarmaUpgradeEffect::Stat(passive), upgrades)
+    }
+}
+
+impl Command for KarmaShop
+{
+    fn apply(self, w: &mut World)
+    {
+        let to_load = self
+            .upgrades
+            .iter()
+            .map(|u| LoadedImage { image: u.icon.clone(), ..default() })
+            .collect();
+        LoadImages(to_load).apply(w);
+        w.insert_resource(self);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Ranks of purchased karma upgrades. Purchases are kept for the rest of the game.
+#[derive(ReactResource, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KarmaUpgrades
+{
+    /// [ upgrade name : rank ]
+    ranks: HashMap<String, usize>,
+}
+
+impl KarmaUpgrades
+{
+    /// Gets the purchased rank of an upgrade. Returns 0 if the upgrade hasn't been purchased.
+    pub fn rank(&self, name: &str) -> usize
+    {
+        self.ranks.get(name).cloned().unwrap_or_default()
+    }
+
+    fn add_rank(&mut self, name: &str)
+    {
+        *self.ranks.entry(name.into()).or_default() += 1;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct KarmaShopPlugin;
+
+impl Plugin for KarmaShopPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.register_command::<KarmaShop>()
+            .init_resource::<KarmaShop>()
+            .init_react_resource::<KarmaUpgrades>()
+            // Runs before `OnEnter(GameState::Play)` so the day's HUD and power-ups see the upgraded limits.
+            .add_systems(OnExit(GameState::DayStart), apply_karma_powerup_upgrades);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
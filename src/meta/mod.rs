@@ -1,9 +1,17 @@
+mod achievements;
 mod audio;
 mod day;
 mod karma;
+mod karma_shop;
 mod plugin;
+mod save;
+mod unlocks;
 
+pub use achievements::*;
 pub use audio::*;
 pub use day::*;
 pub use karma::*;
+pub use karma_shop::*;
 pub use plugin::*;
+pub use save::*;
+pub use unlocks::*;
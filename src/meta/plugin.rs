@@ -12,7 +12,11 @@ impl Plugin for MetaPlugin
     {
         app.add_plugins(DayPlugin)
             .add_plugins(KarmaPlugin)
-            .add_plugins(AudioPlugin);
+            .add_plugins(KarmaShopPlugin)
+            .add_plugins(UnlocksPlugin)
+            .add_plugins(AchievementsPlugin)
+            .add_plugins(AudioPlugin)
+            .add_plugins(SavePlugin);
     }
 }
 
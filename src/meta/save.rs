@@ -0,0 +1,372 @@
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const SAVE_VERSION: u32 = 1;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Applies the save file to meta resources. Runs before the first day starts.
+pub(crate) fn load_save(
+    mut c: Commands,
+    mut file: ResMut<SaveFile>,
+    mut day: ReactResMut<Day>,
+    mut karma: ReactResMut<Karma>,
+    mut karma_upgrades: ReactResMut<KarmaUpgrades>,
+    mut unlocks: ReactResMut<Unlocks>,
+    mut achievements: ReactResMut<AchievementProgress>,
+    mut history: ReactResMut<RunHistory>,
+    mut audio_settings: ReactResMut<AudioSettings>,
+    mut damage_number_settings: ReactResMut<DamageNumberSettings>,
+)
+{
+    file.loaded = true;
+    let Some(save) = file.load() else { return };
+
+    day.get_mut(&mut c).set(save.day.max(1));
+    karma.get_mut(&mut c).set_total(save.karma);
+    *karma_upgrades.get_mut(&mut c) = save.karma_upgrades;
+    *unlocks.get_mut(&mut c) = save.unlocks;
+    *achievements.get_mut(&mut c) = save.achievements;
+    *history.get_mut(&mut c) = save.history;
+    audio_settings.get_mut(&mut c).master_volume = save.settings.master_volume;
+    damage_number_settings.get_mut(&mut c).enabled = save.settings.damage_numbers;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Writes meta resources to the save file.
+fn write_save(
+    file: Option<Res<SaveFile>>,
+    replay: Option<Res<DayReplay>>,
+    play_state: Option<Res<State<PlayState>>>,
+    day: ReactRes<Day>,
+    karma: ReactRes<Karma>,
+    karma_upgrades: ReactRes<KarmaUpgrades>,
+    unlocks: ReactRes<Unlocks>,
+    achievements: ReactRes<AchievementProgress>,
+    history: ReactRes<RunHistory>,
+    audio_settings: ReactRes<AudioSettings>,
+    damage_number_settings: ReactRes<DamageNumberSettings>,
+)
+{
+    let Some(file) = file else { return };
+
+    // Don't overwrite the save with default state before it is loaded.
+    if !file.loaded {
+        return;
+    }
+
+    // Replays overwrite meta state with the recording's state, which shouldn't leak into the player's save.
+    if replay.is_some() {
+        return;
+    }
+
+    // Karma collected during a day is only banked when the day ends. If the day is resumed, the day's karma is
+    // restored from the day snapshot.
+    let in_day = play_state
+        .map(|s| *s.get() == PlayState::Day)
+        .unwrap_or(false);
+    let banked_karma = match in_day {
+        true => karma.total() - karma.day_collected(),
+        false => karma.total(),
+    };
+
+    let save = SaveData {
+        version: SAVE_VERSION,
+        day: day.get(),
+        karma: banked_karma,
+        karma_upgrades: karma_upgrades.clone(),
+        unlocks: unlocks.clone(),
+        achievements: achievements.clone(),
+        history: history.clone(),
+        settings: SavedSettings {
+            master_volume: audio_settings.master_volume,
+            damage_numbers: damage_number_settings.enabled,
+        },
+    };
+    file.save(&save);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Upgrades save data from `version` to `version + 1`.
+///
+/// When incrementing `SAVE_VERSION`, add a migration here for the previous version.
+fn migrate_save_version(version: u32, _data: &mut serde_json::Value) -> Result<(), String>
+{
+    Err(format!("no migration from save version {}", version))
+}
+
+fn parse_save_data(raw: &str) -> Result<SaveData, String>
+{
+    let mut data: serde_json::Value = serde_json::from_str(raw).map_err(|err| err.to_string())?;
+    let version = data
+        .get("version")
+        .and_then(|version| version.as_u64())
+        .ok_or_else(|| String::from("save version is missing"))? as u32;
+    if version > SAVE_VERSION {
+        return Err(format!("save version {} is newer than the supported version {}", version, SAVE_VERSION));
+    }
+
+    for from in version..SAVE_VERSION {
+        migrate_save_version(from, &mut data)?;
+    }
+    data["version"] = SAVE_VERSION.into();
+
+    serde_json::from_value(data).map_err(|err| err.to_string())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSettings
+{
+    pub master_volume: f32,
+    pub damage_numbers: bool,
+}
+
+impl Default for SavedSettings
+{
+    fn default() -> Self
+    {
+        Self { master_volume: 1.0, damage_numbers: true }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Meta progression that persists between sessions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveData
+{
+    pub version: u32,
+    pub day: usize,
+    /// Banked karma.
+    pub karma: usize,
+    #[serde(default)]
+    pub karma_upgrades: KarmaUpgrades,
+    #[serde(default)]
+    pub unlocks: Unlocks,
+    #[serde(default)]
+    pub achievements: AchievementProgress,
+    /// Stats of past days.
+    #[serde(default)]
+    pub history: RunHistory,
+    #[serde(default)]
+    pub settings: SavedSettings,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that enables saving meta progression.
+///
+/// On native the save is a file at `key`. On web the save is a local storage entry named `key`.
+#[derive(Resource, Debug, Clone)]
+pub struct SaveFile
+{
+    key: String,
+    loaded: bool,
+}
+
+impl SaveFile
+{
+    pub fn new(key: impl Into<String>) -> Self
+    {
+        Self { key: key.into(), loaded: false }
+    }
+
+    /// Loads the save. Returns `None` if there is no save or it is unusable, in which case a new game starts.
+    ///
+    /// Unusable saves are backed up so they aren't lost when the next save is written.
+    pub fn load(&self) -> Option<SaveData>
+    {
+        let raw = match self.read() {
+            Ok(Some(raw)) => raw,
+            Ok(None) => {
+                tracing::info!("no save found at {:?}, starting a new game", self.key);
+                return None;
+            }
+            Err(err) => {
+                tracing::error!("failed reading save {:?}, starting a new game: {}", self.key, err);
+                return None;
+            }
+        };
+
+        match parse_save_data(&raw) {
+            Ok(save) => {
+                tracing::info!("loaded save from {:?}", self.key);
+                Some(save)
+            }
+            Err(err) => {
+                let backup = self.backup_key();
+                tracing::error!("failed parsing save {:?}, backing it up to {:?} and starting a new game: {}",
+                    self.key, backup, err);
+                if let Err(err) = Self::write_raw(&backup, &raw) {
+                    tracing::error!("failed backing up save to {:?}: {}", backup, err);
+                }
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, save: &SaveData)
+    {
+        let result = serde_json::to_string(save)
+            .map_err(|err| err.to_string())
+            .and_then(|raw| Self::write_raw(&self.key, &raw));
+        if let Err(err) = result {
+            tracing::error!("failed writing save to {:?}: {}", self.key, err);
+        }
+    }
+
+    /// Reads an extra entry stored alongside the save. Returns `None` if the entry doesn't exist.
+    pub fn read_entry(&self, entry: &str) -> Result<Option<String>, String>
+    {
+        Self::read_raw(&self.entry_key(entry))
+    }
+
+    pub fn write_entry(&self, entry: &str, raw: &str) -> Result<(), String>
+    {
+        Self::write_raw(&self.entry_key(entry), raw)
+    }
+
+    pub fn remove_entry(&self, entry: &str) -> Result<(), String>
+    {
+        Self::remove_raw(&self.entry_key(entry))
+    }
+
+    fn entry_key(&self, entry: &str) -> String
+    {
+        format!("{}.{}", self.key, entry)
+    }
+
+    fn backup_key(&self) -> String
+    {
+        self.entry_key("corrupt")
+    }
+
+    fn read(&self) -> Result<Option<String>, String>
+    {
+        Self::read_raw(&self.key)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn read_raw(key: &str) -> Result<Option<String>, String>
+    {
+        match std::fs::read_to_string(key) {
+            Ok(raw) => Ok(Some(raw)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn write_raw(key: &str, raw: &str) -> Result<(), String>
+    {
+        // Write to a temporary file first so a crash mid-write can't corrupt the existing save.
+        let tmp = format!("{}.tmp", key);
+        std::fs::write(&tmp, raw).map_err(|err| err.to_string())?;
+        std::fs::rename(&tmp, key).map_err(|err| err.to_string())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn remove_raw(key: &str) -> Result<(), String>
+    {
+        match std::fs::remove_file(key) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn local_storage() -> Result<web_sys::Storage, String>
+    {
+        web_sys::window()
+            .ok_or_else(|| String::from("window is unavailable"))?
+            .local_storage()
+            .map_err(|err| format!("{:?}", err))?
+            .ok_or_else(|| String::from("local storage is unavailable"))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn read_raw(key: &str) -> Result<Option<String>, String>
+    {
+        Self::local_storage()?
+            .get_item(key)
+            .map_err(|err| format!("{:?}", err))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn write_raw(key: &str, raw: &str) -> Result<(), String>
+    {
+        Self::local_storage()?
+            .set_item(key, raw)
+            .map_err(|err| format!("{:?}", err))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn remove_raw(key: &str) -> Result<(), String>
+    {
+        Self::local_storage()?
+            .remove_item(key)
+            .map_err(|err| format!("{:?}", err))
+    }
+}
+
+impl Default for SaveFile
+{
+    fn default() -> Self
+    {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Self::new("save.json")
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self::new("srs_bjam5_save")
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Loads and writes the save if [`SaveFile`] exists.
+///
+/// The save is written when a day ends, when the next day starts (after the day is advanced and karma is banked),
+/// and when settings, upgrades, or achievements change.
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.add_systems(
+            OnExit(GameState::Loading),
+            load_save.run_if(resource_exists::<SaveFile>),
+        )
+        .react(|rc| {
+            rc.on_persistent(
+                (
+                    broadcast::<GameDayOver>(),
+                    broadcast::<GameDayStart>(),
+                    resource_mutation::<KarmaUpgrades>(),
+                    resource_mutation::<Unlocks>(),
+                    resource_mutation::<AchievementProgress>(),
+                    resource_mutation::<RunHistory>(),
+                    resource_mutation::<AudioSettings>(),
+                    resource_mutation::<DamageNumberSettings>(),
+                ),
+                write_save,
+            )
+        });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
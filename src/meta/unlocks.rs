@@ -0,0 +1,44 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+use serde::{Deserialize, Serialize};
+
+//use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Content unlocked through meta progression. Unlocks are kept for the rest of the game.
+#[derive(ReactResource, Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Unlocks
+{
+    unlocked: HashSet<String>,
+}
+
+impl Unlocks
+{
+    pub fn is_unlocked(&self, name: &str) -> bool
+    {
+        self.unlocked.contains(name)
+    }
+
+    /// Returns `false` if the content was already unlocked.
+    pub fn unlock(&mut self, name: impl Into<String>) -> bool
+    {
+        self.unlocked.insert(name.into())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub struct UnlocksPlugin;
+
+impl Plugin for UnlocksPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.init_react_resource::<Unlocks>();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
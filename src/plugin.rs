@@ -92,6 +92,54 @@ fn setup_camera(mut c: Commands)
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Plugin with all game content and dependencies that don't depend on a window.
+///
+/// Used by both [`AppPlugin`] and [`HeadlessAppPlugin`]. Bevy's core plugins must be added before this.
+pub struct GameCorePlugin;
+
+impl Plugin for GameCorePlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app
+            // Dependencies
+            .add_plugins(TilemapPlugin)
+            .add_plugins(SpritesheetAnimationPlugin)
+            .add_plugins(ReactPlugin)
+            .add_plugins(SickleUiPlugin)
+            .add_plugins(CobwebUiPlugin)
+            // Utils
+            .add_plugins(UtilsPlugin) // must be added after CobwebUiPlugin
+            // Game content
+            .add_plugins(ControlsPlugin)
+            .add_plugins(SettingsPlugin)
+            .add_plugins(GameConstantsPlugin)
+            .add_plugins(MetaPlugin)
+            .add_plugins(DayStartPlugin)
+            .add_plugins(GamePlugin)
+            .add_plugins(ReplayPlugin)
+            // Load all assets
+            .load("manifest.caf.json")
+            // Misc setup and game management
+            .add_sub_state::<GameState>()
+            .add_sub_state::<PlayState>()
+            .enable_state_scoped_entities::<GameState>()
+            .add_systems(Startup, setup_camera)
+            .add_systems(OnEnter(LoadState::Done), handle_loading_done)
+            .add_systems(
+                OnEnter(GameState::DayStart),
+                broadcast_event("GameState::DayStart", GameDayStart),
+            )
+            .add_systems(OnEnter(GameState::Play), broadcast_event("GameState::Play", GamePlay))
+            .add_systems(
+                OnEnter(PlayState::DayOver),
+                broadcast_event("PlayState::DayOver", GameDayOver),
+            );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 pub struct AppPlugin;
 
 impl Plugin for AppPlugin
@@ -112,38 +160,8 @@ impl Plugin for AppPlugin
                 .set(ImagePlugin::default_nearest())
                 .set(AssetPlugin { meta_check: AssetMetaCheck::Never, ..default() }),
         )
-        // Dependencies
-        .add_plugins(TilemapPlugin)
-        .add_plugins(SpritesheetAnimationPlugin)
-        .add_plugins(ReactPlugin)
-        .add_plugins(SickleUiPlugin)
-        .add_plugins(CobwebUiPlugin)
-        // Utils
-        .add_plugins(UtilsPlugin) // must be added after CobwebUiPlugin
-        // Game content
-        .add_plugins(ControlsPlugin)
-        .add_plugins(SettingsPlugin)
-        .add_plugins(GameConstantsPlugin)
-        .add_plugins(MetaPlugin)
-        .add_plugins(DayStartPlugin)
-        .add_plugins(GamePlugin)
-        // Load all assets
-        .load("manifest.caf.json")
-        // Misc setup and game management
-        .add_sub_state::<GameState>()
-        .add_sub_state::<PlayState>()
-        .enable_state_scoped_entities::<GameState>()
-        .add_systems(Startup, setup_camera)
-        .add_systems(OnEnter(LoadState::Done), handle_loading_done)
-        .add_systems(
-            OnEnter(GameState::DayStart),
-            broadcast_event("GameState::DayStart", GameDayStart),
-        )
-        .add_systems(OnEnter(GameState::Play), broadcast_event("GameState::Play", GamePlay))
-        .add_systems(
-            OnEnter(PlayState::DayOver),
-            broadcast_event("PlayState::DayOver", GameDayOver),
-        );
+        .add_plugins(GameCorePlugin)
+        .init_resource::<SaveFile>();
 
         #[cfg(feature = "dev")]
         {
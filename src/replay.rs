@@ -0,0 +1,458 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::time::{TimeSystem, TimeUpdateStrategy};
+use bevy_cobweb::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const RECORDING_VERSION: u32 = 1;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn start_recording(
+    mut recorder: ResMut<DayRecorder>,
+    rng: Res<GameRng>,
+    day: ReactRes<Day>,
+    controls: ReactRes<Controls>,
+    karma_upgrades: ReactRes<KarmaUpgrades>,
+    unlocks: ReactRes<Unlocks>,
+)
+{
+    recorder.recording = DayRecording {
+        version: RECORDING_VERSION,
+        day: day.get(),
+        rng: rng.state(),
+        controls: controls.clone(),
+        karma_upgrades: karma_upgrades.clone(),
+        unlocks: unlocks.clone(),
+        ..default()
+    };
+    recorder.pending_rerolls = 0;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn record_frame(
+    mut recorder: ResMut<DayRecorder>,
+    time: Res<Time<Virtual>>,
+    clock: Res<GameClock>,
+    button_input: Res<ButtonInput<KeyCode>>,
+    controls: ReactRes<Controls>,
+)
+{
+    // Frames while paused don't advance the game, so we don't need them.
+    if time.is_paused() {
+        return;
+    }
+
+    recorder.recording.frames.push(RecordedFrame {
+        delta: clock.delta,
+        movement: MovementInput::from_input(&button_input, &controls),
+    });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn save_recording(recorder: Res<DayRecorder>)
+{
+    match recorder.save() {
+        Ok(()) => tracing::info!("saved day recording to {:?}", recorder.path),
+        Err(err) => tracing::error!("failed saving day recording to {:?}: {}", recorder.path, err),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn prepare_replay_day(
+    mut c: Commands,
+    replay: Res<DayReplay>,
+    mut day: ReactResMut<Day>,
+    mut karma_upgrades: ReactResMut<KarmaUpgrades>,
+    mut unlocks: ReactResMut<Unlocks>,
+)
+{
+    day.get_mut(&mut c).set(replay.recording.day);
+    *karma_upgrades.get_mut(&mut c) = replay.recording.karma_upgrades.clone();
+    *unlocks.get_mut(&mut c) = replay.recording.unlocks.clone();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn start_replay(
+    mut c: Commands,
+    mut replay: ResMut<DayReplay>,
+    mut rng: ResMut<GameRng>,
+    mut controls: ReactResMut<Controls>,
+    strategy: Res<TimeUpdateStrategy>,
+)
+{
+    replay.prev_manual_delta = match *strategy {
+        TimeUpdateStrategy::ManualDuration(delta) => Some(delta),
+        _ => None,
+    };
+    rng.set_state(&replay.recording.rng);
+    *controls.get_mut(&mut c) = replay.recording.controls.clone();
+    replay.next_frame = 0;
+    replay.next_powerup = 0;
+    replay.current_movement = MovementInput::default();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sets the time delta of the current frame from the recording.
+fn feed_replay_delta(
+    mut replay: ResMut<DayReplay>,
+    time: Res<Time<Virtual>>,
+    mut strategy: ResMut<TimeUpdateStrategy>,
+)
+{
+    // Recordings don't contain paused frames.
+    if time.is_paused() {
+        return;
+    }
+
+    let Some(frame) = replay.recording.frames.get(replay.next_frame).cloned() else {
+        if replay.next_frame == replay.recording.frames.len() {
+            tracing::info!("day replay finished after {} frames", replay.next_frame);
+            replay.next_frame += 1;
+            replay.restore_time_update_strategy(&mut strategy);
+        }
+        replay.current_movement = MovementInput::default();
+        return;
+    };
+
+    *strategy = TimeUpdateStrategy::ManualDuration(frame.delta);
+    replay.current_movement = frame.movement;
+    replay.next_frame += 1;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Stops driving time from the recording if the day ended before the replay finished.
+fn end_replay_day(replay: Res<DayReplay>, mut strategy: ResMut<TimeUpdateStrategy>)
+{
+    replay.restore_time_update_strategy(&mut strategy);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Overwrites movement inputs with the recorded inputs of the current frame.
+fn inject_replay_inputs(
+    replay: Res<DayReplay>,
+    mut button_input: ResMut<ButtonInput<KeyCode>>,
+    controls: ReactRes<Controls>,
+)
+{
+    replay
+        .current_movement
+        .apply_to_input(&mut button_input, &controls);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Movement keys pressed in a frame.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MovementInput
+{
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool,
+}
+
+impl MovementInput
+{
+    pub fn from_input(input: &ButtonInput<KeyCode>, controls: &Controls) -> Self
+    {
+        Self {
+            up: input.pressed(*controls.move_up),
+            down: input.pressed(*controls.move_down),
+            left: input.pressed(*controls.move_left),
+            right: input.pressed(*controls.move_right),
+        }
+    }
+
+    pub fn apply_to_input(&self, input: &mut ButtonInput<KeyCode>, controls: &Controls)
+    {
+        for (pressed, key) in [
+            (self.up, *controls.move_up),
+            (self.down, *controls.move_down),
+            (self.left, *controls.move_left),
+            (self.right, *controls.move_right),
+        ] {
+            match pressed {
+                true => {
+                    if !input.pressed(key) {
+                        input.press(key);
+                    }
+                }
+                false => input.release(key),
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A single unpaused frame of a day.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedFrame
+{
+    /// `GameClock` delta of the frame.
+    pub delta: Duration,
+    pub movement: MovementInput,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A power-up option selected by the player.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedPowerup
+{
+    /// Index of the frame where the option was selected.
+    pub frame: usize,
+    pub option: PowerupOption,
+    /// Number of times the options were rerolled before this option was selected.
+    #[serde(default)]
+    pub rerolls: usize,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Everything needed to deterministically reproduce a day.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayRecording
+{
+    pub version: u32,
+    pub day: usize,
+    /// State of `GameRng` when the day started.
+    pub rng: GameRngState,
+    pub controls: Controls,
+    /// Karma upgrades owned when the day started.
+    #[serde(default)]
+    pub karma_upgrades: KarmaUpgrades,
+    /// Content unlocked when the day started.
+    #[serde(default)]
+    pub unlocks: Unlocks,
+    pub frames: Vec<RecordedFrame>,
+    pub powerups: Vec<RecordedPowerup>,
+}
+
+impl DayRecording
+{
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String>
+    {
+        let bytes = std::fs::read(path).map_err(|err| err.to_string())?;
+        let recording: Self = serde_json::from_slice(&bytes).map_err(|err| err.to_string())?;
+        if recording.version != RECORDING_VERSION {
+            return Err(format!(
+                "recording version {} is not supported (expected {})",
+                recording.version, RECORDING_VERSION
+            ));
+        }
+        Ok(recording)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that records the current day to a file.
+///
+/// The file is overwritten when each day ends.
+#[derive(Resource, Debug)]
+pub struct DayRecorder
+{
+    path: PathBuf,
+    recording: DayRecording,
+    /// Rerolls of the power-up currently being selected.
+    pending_rerolls: usize,
+}
+
+impl DayRecorder
+{
+    pub fn new(path: impl Into<PathBuf>) -> Self
+    {
+        Self {
+            path: path.into(),
+            recording: DayRecording::default(),
+            pending_rerolls: 0,
+        }
+    }
+
+    pub fn record_powerup_reroll(&mut self)
+    {
+        self.pending_rerolls += 1;
+    }
+
+    pub fn record_powerup(&mut self, option: PowerupOption)
+    {
+        let frame = self.recording.frames.len();
+        let rerolls = std::mem::take(&mut self.pending_rerolls);
+        self.recording
+            .powerups
+            .push(RecordedPowerup { frame, option, rerolls });
+    }
+
+    pub fn save(&self) -> Result<(), String>
+    {
+        let bytes = serde_json::to_vec(&self.recording).map_err(|err| err.to_string())?;
+        std::fs::write(&self.path, bytes).map_err(|err| err.to_string())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resource that replays a [`DayRecording`].
+///
+/// Movement inputs are injected into `ButtonInput<KeyCode>` and frame deltas are applied with
+/// `TimeUpdateStrategy::ManualDuration`. Power-up selections are taken from the recording instead of showing the
+/// power-up UI.
+///
+/// Once the recording runs out (or the day ends), the time update strategy from before the replay is restored and
+/// no more inputs are injected.
+#[derive(Resource, Debug)]
+pub struct DayReplay
+{
+    recording: DayRecording,
+    next_frame: usize,
+    next_powerup: usize,
+    current_movement: MovementInput,
+    /// Frame delta of the `TimeUpdateStrategy` in effect before the replay started. `None` means
+    /// `TimeUpdateStrategy::Automatic`.
+    prev_manual_delta: Option<Duration>,
+}
+
+impl DayReplay
+{
+    pub fn new(recording: DayRecording) -> Self
+    {
+        Self {
+            recording,
+            next_frame: 0,
+            next_powerup: 0,
+            current_movement: MovementInput::default(),
+            prev_manual_delta: None,
+        }
+    }
+
+    fn restore_time_update_strategy(&self, strategy: &mut TimeUpdateStrategy)
+    {
+        *strategy = match self.prev_manual_delta {
+            Some(delta) => TimeUpdateStrategy::ManualDuration(delta),
+            None => TimeUpdateStrategy::Automatic,
+        };
+    }
+
+    /// Gets the number of times the next recorded power-up's options were rerolled.
+    pub fn next_powerup_rerolls(&self) -> usize
+    {
+        self.recording
+            .powerups
+            .get(self.next_powerup)
+            .map(|recorded| recorded.rerolls)
+            .unwrap_or_default()
+    }
+
+    /// Gets the next recorded power-up selection.
+    ///
+    /// Logs a warning if the replay has diverged from the recording.
+    pub fn next_powerup(&mut self, options: &[PowerupOption]) -> Option<PowerupOption>
+    {
+        let Some(recorded) = self.recording.powerups.get(self.next_powerup).cloned() else {
+            tracing::warn!("replay desync: no recorded power-up remaining, {} frames in", self.current_frame());
+            return None;
+        };
+        self.next_powerup += 1;
+
+        if recorded.frame != self.current_frame() {
+            tracing::warn!("replay desync: power-up {:?} was recorded in frame {} but offered in frame {}",
+                recorded.option, recorded.frame, self.current_frame());
+        }
+        if !options.contains(&recorded.option) {
+            tracing::warn!("replay desync: recorded power-up {:?} is not in offered options {:?}",
+                recorded.option, options);
+        }
+
+        Some(recorded.option)
+    }
+
+    /// The number of recorded frames that have been replayed.
+    pub fn current_frame(&self) -> usize
+    {
+        self.next_frame.min(self.recording.frames.len())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// System set in `OnEnter(PlayState::Day)` where the day's starting `GameRng` state is recorded or restored.
+///
+/// Systems that use `GameRng` when a day starts must run after this set.
+#[derive(SystemSet, Hash, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct DayRngSetupSet;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records days if [`DayRecorder`] exists, and replays a day if [`DayReplay`] exists.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin
+{
+    fn build(&self, app: &mut App)
+    {
+        app.add_systems(
+            OnEnter(PlayState::Day),
+            start_recording
+                .in_set(DayRngSetupSet)
+                .run_if(resource_exists::<DayRecorder>),
+        )
+        .add_systems(
+            PreUpdate,
+            record_frame
+                .after(InputSystem)
+                .after(GameClockUpdateSet)
+                .run_if(resource_exists::<DayRecorder>)
+                .run_if(in_state(PlayState::Day)),
+        )
+        .add_systems(
+            OnEnter(PlayState::DayOver),
+            save_recording.run_if(resource_exists::<DayRecorder>),
+        )
+        .add_systems(
+            OnEnter(GameState::DayStart),
+            prepare_replay_day.run_if(resource_exists::<DayReplay>),
+        )
+        .add_systems(
+            OnEnter(PlayState::Day),
+            start_replay
+                .in_set(DayRngSetupSet)
+                .run_if(resource_exists::<DayReplay>),
+        )
+        .add_systems(
+            OnExit(PlayState::Day),
+            end_replay_day.run_if(resource_exists::<DayReplay>),
+        )
+        .add_systems(
+            First,
+            feed_replay_delta
+                .before(TimeSystem)
+                .run_if(resource_exists::<DayReplay>)
+                .run_if(in_state(PlayState::Day)),
+        )
+        .add_systems(
+            PreUpdate,
+            inject_replay_inputs
+                .after(InputSystem)
+                .run_if(resource_exists::<DayReplay>)
+                .run_if(in_state(PlayState::Day)),
+        );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,3 +1,4 @@
+use bevy::app::AppExit;
 use bevy::ecs::system::EntityCommands;
 use bevy::prelude::*;
 use bevy_cobweb::prelude::*;
@@ -217,8 +218,43 @@ fn spawn_settings_menu(mut c: Commands, mut s: ResMut<SceneLoader>, audio_settin
                 adjust_sickle_slider_theme(&mut n.entity_commands());
             });
 
+            l.edit("damage_numbers::button", |l| {
+                l.on_pressed(|mut c: Commands, mut settings: ReactResMut<DamageNumberSettings>| {
+                    let settings = settings.get_mut(&mut c);
+                    settings.enabled = !settings.enabled;
+                });
+                l.edit("text", |l| {
+                    l.update_on(resource_mutation::<DamageNumberSettings>(), |id| {
+                        move |mut e: TextEditor, settings: ReactRes<DamageNumberSettings>| {
+                            let text = if settings.enabled { "On" } else { "Off" };
+                            write_text!(e, id, "{}", text);
+                        }
+                    });
+                });
+            });
+
             // todo: restart from day 1 button
 
+            l.edit("footer::save_quit_button", |l| {
+                // Days can only be saved while they are being played.
+                l.update_on((), |id| {
+                    move |mut c: Commands, state: Option<Res<State<PlayState>>>, replay: Option<Res<DayReplay>>| {
+                        let in_day = state.map(|s| *s.get() == PlayState::Day).unwrap_or(false);
+                        let next = match in_day && replay.is_none() {
+                            true => DisplayControl::Display,
+                            false => DisplayControl::Hide,
+                        };
+                        c.entity(id).insert_reactive(next);
+                    }
+                });
+                l.on_pressed(|w: &mut World| {
+                    if !w.syscall((), save_day_snapshot) {
+                        return;
+                    }
+                    w.send_event(AppExit::Success);
+                });
+            });
+
             l.edit("footer::close_button", |l| {
                 l.on_pressed(|mut c: Commands| {
                     c.react().broadcast(ToggleSettings);
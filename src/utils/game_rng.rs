@@ -1,6 +1,18 @@
 use bevy::prelude::*;
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Captures the exact state of a [`GameRng`] so it can be restored later.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRngState
+{
+    pub seed: [u8; 32],
+    pub stream: u64,
+    pub word_pos: u128,
+}
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -22,6 +34,22 @@ impl GameRng
     {
         &mut self.rng
     }
+
+    pub fn state(&self) -> GameRngState
+    {
+        GameRngState {
+            seed: self.rng.get_seed(),
+            stream: self.rng.get_stream(),
+            word_pos: self.rng.get_word_pos(),
+        }
+    }
+
+    pub fn set_state(&mut self, state: &GameRngState)
+    {
+        self.rng = ChaCha8Rng::from_seed(state.seed);
+        self.rng.set_stream(state.stream);
+        self.rng.set_word_pos(state.word_pos);
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------